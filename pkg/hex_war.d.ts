@@ -1,35 +1,301 @@
 /* tslint:disable */
 /* eslint-disable */
 /**
- * Initialize with *per-team* count (0..=5) and speed
+ * Fix the world at `width`×`height` world units, scaled and letterboxed into the canvas,
+ * so the board is the same on every screen; `0, 0` follows the canvas size again. Both
+ * sides must be 100..=10000. Restarts the match.
  */
+export function set_world_size(width: number, height: number): void;
+/**
+ * Territory chart overlay. `x`/`y` are CSS px from the top-left corner; negative values
+ * anchor to the right/bottom edge. `opacity` is 0..=1.
+ */
+export function set_chart_overlay(enabled: boolean, x: number, y: number, width: number, height: number, opacity: number): void;
+/**
+ * Toroidal arena: balls leaving one edge reappear on the opposite one, and claiming,
+ * collisions and pickups reach across the seam. Turning it off pulls balls back inside.
+ * The hexes are laid out to tile across the seams, so either way the board restarts.
+ */
+export function set_wrap(on: boolean): void;
 export function init_app(canvas_id: string, css_w: number, css_h: number, balls_per_team: number, speed: number): void;
-export function start(): void;
-export function stop(): void;
-export function reset_grid(): void;
+/**
+ * Particle effects (impact sparks, capture chips, wall dust): 0 turns them off, 1 is the
+ * default amount, up to 3. Visual only.
+ */
+export function set_particles(intensity: number): void;
+/**
+ * Power-up spawn rules: on/off, seconds between spawns, max pickups on board, effect duration.
+ */
+export function set_powerups(enabled: boolean, interval_secs: number, max_active: number, duration_secs: number): void;
+/**
+ * Place a force field centred on world point `(x, y)`. `kind` is
+ * "attract", "repel", "wind" (pushing along `angle_deg`, 0 = right, 90 = down) or
+ * "vortex" (clockwise; negative `strength` turns it anticlockwise). `strength` is the
+ * peak acceleration in px/s² (up to 5000), fading to nothing at `radius` (up to 20000)
+ * except for wind; a `radius` of 0 covers the whole arena. Fields turn balls without changing their speed.
+ */
+export function add_force_field(kind: string, x: number, y: number, radius: number, strength: number, angle_deg: number): void;
+/**
+ * How hexes animate when they change owner: "none", "fade", "pop" or "rotate", over
+ * `duration_secs` of sim time (0..=2). Visual only; scoring is unaffected.
+ */
+export function set_flip_animation(style: string, duration_secs: number): void;
+/**
+ * World coordinates `[x, y]` under canvas CSS pixel `(x, y)`, through the letterbox and camera.
+ */
+export function screen_to_world(x: number, y: number): Float64Array;
+/**
+ * The current board rendered in software and encoded as PNG, `scale` device pixels per
+ * CSS pixel (clamped to 0.25..=4). Overlays and power-up glyphs are not included.
+ */
+export function export_png(scale: number): Uint8Array;
+/**
+ * Keyboard steering for `team`'s player ball; `(x,y)` is a direction, clamped to unit length.
+ */
+export function player_steer(team: string, x: number, y: number): void;
+/**
+ * Mark `team`'s first ball as player-controlled (or release it back to the simulation).
+ */
+export function set_player_control(team: string, enabled: boolean): void;
+/**
+ * Current world size in world units (the canvas size unless fixed with `set_world_size`).
+ */
+export function world_width(): number;
 export function set_speed(multiplier: number): void;
+export function clear_powerup_spawns(): void;
 /**
- * Set balls *per team* (0..=5)
+ * Round-robin tournament over headless matches. `entrants` is a comma-separated list of
+ * `controller` or `controller:heavy/light` specs (e.g. `"regions,chase:2/0,none"`); every
+ * pair plays each seed twice with sides swapped. `seeds` is capped at 20, `match_secs` at
+ * 300, entrants at 8 and the whole run at 20000 simulated seconds. Returns Elo ratings and
+ * a win matrix as `format` "csv" or "json".
  */
-export function set_balls_per_team(n: number): void;
+export function run_tournament(entrants: string, seeds: number, match_secs: number, balls_per_team: number, format: string): string;
 /**
- * Back-compat alias (treat input as per-team)
+ * Pan by a drag of `(dx, dy)` canvas CSS pixels.
  */
-export function set_num_balls(n: number): void;
+export function camera_pan(dx: number, dy: number): void;
+export function reset_grid(): void;
+/**
+ * Assign a built-in AI to `team`: "regions", "defend", "chase", "wander", or "none".
+ */
+export function set_team_controller(team: string, name: string): void;
+/**
+ * Grid by hex radius in world units; 0 goes back to the automatic size for the world.
+ * Rebuilds the grid, respawns balls sized to the new hexes and restarts the match. A later
+ * resize that would take the grid past 250k cells goes back to the automatic size.
+ */
+export function set_hex_radius(radius: number): void;
+/**
+ * Per-team spawn mix: of each team's balls, `heavy` spawn big/slow and `light` small/fast.
+ */
+export function set_ball_mix(team: string, heavy: number, light: number): void;
 export function resize(css_w: number, css_h: number): void;
+export function clear_spawn_zones(): void;
+/**
+ * Switch colour theme by name (see `THEME_NAMES`); applies from the next frame, or at once
+ * while stopped. The match carries on untouched.
+ */
+export function set_theme(name: string): void;
+/**
+ * Enable or disable one kind: "speed", "grow", "paint_bomb", "shield" or "split".
+ */
+export function set_powerup_kind(kind: string, enabled: boolean): void;
+/**
+ * Back to the whole arena.
+ */
+export function camera_reset(): void;
+/**
+ * Remove the ball with `id`; returns false if there is none.
+ */
+export function remove_ball(id: number): boolean;
+/**
+ * Per-hex activity since the last reset:
+ * `{ cols, rows, flips, white_secs, black_secs, longest_owner }`, each matrix indexed `[row][col]`.
+ */
+export function get_heatmap(): any;
+/**
+ * Draw force fields as faint arrows. Visual only.
+ */
+export function set_show_fields(on: boolean): void;
+export function start(): void;
+/**
+ * Grid by count: the largest hexes giving at least `cols` columns and `rows` rows (0 leaves
+ * that axis free), e.g. `300, 0` for a dense board or `15, 0` for a chunky one. Rebuilds
+ * like `set_hex_radius`.
+ */
+export function set_grid_size(cols: number, rows: number): void;
+/**
+ * The current board as a standalone SVG document: hexes, glossy balls, and (optionally)
+ * a score caption. Uses the same geometry as the canvas renderer.
+ */
+export function export_svg(caption: boolean): string;
+/**
+ * Per-team handicap for balance experiments: `extra_balls` (-5..=5) added to the balls
+ * per team, multipliers (0.25..=4) on spawn speed, ball radius and claim radius, and a
+ * cruising speed cap `max_speed` in px/s (50..=2000, 0 for the default). Balls are never
+ * spawned too big to fit the world, whatever the radius multiplier. Respawns balls;
+ * `set_handicap(team, 0, 1, 1, 1, 0)` clears it.
+ */
+export function set_handicap(team: string, extra_balls: number, speed: number, radius: number, claim: number, max_speed: number): void;
+/**
+ * Fill hexes by "owner" (team colour) or "heat" (how often each hex flipped).
+ */
+export function set_cell_view(mode: string): void;
+export function clear_force_fields(): void;
+/**
+ * Headless fair match: `seed` (0 picks one) played twice between two entrant specs as in
+ * `run_tournament`, `first` taking white in leg one and black in leg two, so both get each
+ * colour, side and spawn. Legs last at most 300 sim seconds. `layout` as in `set_spawn_layout`.
+ * Returns the `get_fair_match` JSON.
+ */
+export function run_fair_match(first: string, second: string, seed: number, match_secs: number, balls_per_team: number, layout: string): string;
+export function set_num_balls(n: number): void;
+/**
+ * Override the current theme's ball trails: `kind` is "off", "line" or "ghosts", `length`
+ * the number of frames kept (up to 48), `opacity` at the ball end. `set_theme` restores
+ * the theme's own trails.
+ */
+export function set_trails(kind: string, length: number, opacity: number): void;
+/**
+ * Play the current setup as a two-leg fair match of `leg_secs` sim seconds per leg. Both
+ * legs start from `seed` (0 picks one); for the second, the teams' controllers, ball mixes
+ * and player control swap colours, so each side plays both spawns. Starts the loop; it
+ * stops after the second leg. Poll `get_fair_match` for progress and the verdict.
+ */
+export function start_fair_match(leg_secs: number, seed: number): void;
+/**
+ * Match statistics since the last reset:
+ * `{ time, teams: { white, black }, balls: [...], series: { interval, t, white, black, points_white, points_black } }`.
+ * Per ball: id, cells flipped/stolen, distance, collisions, wall and territory bounces, peak speed.
+ */
+export function get_stats(): any;
+/**
+ * What `edge` ("left", "right", "top", "bottom" or "all") does to balls that hit it:
+ * - "elastic": perfect mirror
+ * - "damped": mirror keeping `amount` (0..=1) of the ball's cruising speed
+ * - "scatter": mirror turned by a random angle of up to `amount` degrees (0..=80)
+ * - "sticky": holds the ball for `amount` seconds (0..=5), then releases it mirrored
+ * - "white" / "black": owned by that team, whose balls pass through and come back on the
+ *   opposite edge while the enemy bounces
+ *
+ * Walls are ignored while wrap-around is on.
+ */
+export function set_wall(edge: string, kind: string, amount: number): void;
+/**
+ * Where balls start: "sides" (random in each team's quarter), "mirrored" (black is the
+ * point reflection of white), "line" or "wedge" formations, "zones" (inside the areas
+ * from `add_spawn_zone`) or "random" (anywhere). Respawns balls.
+ */
+export function set_spawn_layout(name: string): void;
+/**
+ * Zoom by `factor` (1 = no change) about canvas CSS pixel `(x, y)`; zoom stays in 1..=8.
+ */
+export function camera_zoom_at(x: number, y: number, factor: number): void;
+/**
+ * Fair match progress: `{ seed, leg, leg_secs, elapsed, swapped, legs: [{ white, black }],
+ * first, second, verdict }`. `leg` is 1 or 2 while playing and 0 once done; `swapped` is
+ * true while the sides hold each other's colours, so player input for the side that
+ * started as white goes to "black" and vice versa; `first` and `second` are combined
+ * points of the side that started as white and black; `verdict` is "first", "second" or
+ * "draw" when done, otherwise null. Null if no fair match was started.
+ */
+export function get_fair_match(): any;
+export function stop(): void;
+export function set_balls_per_team(n: number): void;
+export function clear_player_target(team: string): void;
+/**
+ * Reduced-motion mode: no trails, particles or flip animations (hexes change instantly).
+ * The page sets this from `prefers-reduced-motion`.
+ */
+export function set_reduced_motion(on: boolean): void;
+/**
+ * Pointer steering: `team`'s player ball turns toward the world point under canvas CSS
+ * pixel `(x,y)`.
+ */
+export function player_target(team: string, x: number, y: number): void;
+/**
+ * Pin power-up spawns to a hex; once any are set, pickups only appear on these.
+ */
+export function add_powerup_spawn(col: number, row: number): void;
+/**
+ * Add a spawn area for `team` in world units, used by the "zones" layout. A team without
+ * zones spawns as in "sides". Takes effect at the next respawn.
+ */
+export function add_spawn_zone(team: string, x: number, y: number, width: number, height: number): void;
+export function world_height(): number;
+/**
+ * Id of the topmost ball covering world point `(x, y)`, if any.
+ */
+export function ball_at(x: number, y: number): number | undefined;
+/**
+ * Drop a `team` ball at world point `(x, y)` with velocity `(vx, vy)` px/s and `radius`
+ * (up to 60) into the running match, leaving the other balls and the grid alone. A zero
+ * velocity picks a random heading at the standard speed; a zero radius the standard
+ * size. Radii too big for the world are shrunk to fit, and speeds under 60 px/s raised to
+ * that. Returns the ball's id, which stays valid until the ball is removed or the team is
+ * respawned.
+ */
+export function add_ball(team: string, x: number, y: number, vx: number, vy: number, radius: number): number;
 
 export type InitInput = RequestInfo | URL | Response | BufferSource | WebAssembly.Module;
 
 export interface InitOutput {
   readonly memory: WebAssembly.Memory;
+  readonly add_ball: (a: number, b: number, c: number, d: number, e: number, f: number, g: number) => [number, number, number];
+  readonly add_force_field: (a: number, b: number, c: number, d: number, e: number, f: number, g: number) => [number, number];
+  readonly add_spawn_zone: (a: number, b: number, c: number, d: number, e: number, f: number) => [number, number];
+  readonly ball_at: (a: number, b: number) => number;
+  readonly camera_pan: (a: number, b: number) => void;
+  readonly camera_zoom_at: (a: number, b: number, c: number) => void;
+  readonly clear_player_target: (a: number, b: number) => [number, number];
+  readonly export_png: (a: number) => [number, number, number, number];
+  readonly export_svg: (a: number) => [number, number, number, number];
+  readonly get_fair_match: () => [number, number, number];
+  readonly get_heatmap: () => [number, number, number];
+  readonly get_stats: () => [number, number, number];
   readonly init_app: (a: number, b: number, c: number, d: number, e: number, f: number) => [number, number];
+  readonly player_steer: (a: number, b: number, c: number, d: number) => [number, number];
+  readonly player_target: (a: number, b: number, c: number, d: number) => [number, number];
+  readonly remove_ball: (a: number) => number;
+  readonly run_fair_match: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: number) => [number, number, number, number];
+  readonly run_tournament: (a: number, b: number, c: number, d: number, e: number, f: number, g: number) => [number, number, number, number];
+  readonly screen_to_world: (a: number, b: number) => [number, number];
+  readonly set_ball_mix: (a: number, b: number, c: number, d: number) => [number, number];
+  readonly set_cell_view: (a: number, b: number) => [number, number];
+  readonly set_chart_overlay: (a: number, b: number, c: number, d: number, e: number, f: number) => void;
+  readonly set_flip_animation: (a: number, b: number, c: number) => [number, number];
+  readonly set_grid_size: (a: number, b: number) => [number, number];
+  readonly set_handicap: (a: number, b: number, c: number, d: number, e: number, f: number, g: number) => [number, number];
+  readonly set_hex_radius: (a: number) => [number, number];
+  readonly set_player_control: (a: number, b: number, c: number) => [number, number];
+  readonly set_powerup_kind: (a: number, b: number, c: number) => [number, number];
+  readonly set_powerups: (a: number, b: number, c: number, d: number) => void;
+  readonly set_reduced_motion: (a: number) => void;
+  readonly set_show_fields: (a: number) => void;
+  readonly set_spawn_layout: (a: number, b: number) => [number, number];
+  readonly set_team_controller: (a: number, b: number, c: number, d: number) => [number, number];
+  readonly set_theme: (a: number, b: number) => [number, number];
+  readonly set_trails: (a: number, b: number, c: number, d: number) => [number, number];
+  readonly set_wall: (a: number, b: number, c: number, d: number, e: number) => [number, number];
+  readonly set_world_size: (a: number, b: number) => [number, number];
+  readonly set_wrap: (a: number) => void;
   readonly start: () => [number, number];
-  readonly stop: () => void;
+  readonly start_fair_match: (a: number, b: number) => [number, number];
+  readonly set_balls_per_team: (a: number) => void;
+  readonly set_num_balls: (a: number) => void;
+  readonly camera_reset: () => void;
+  readonly add_powerup_spawn: (a: number, b: number) => void;
+  readonly world_height: () => number;
+  readonly world_width: () => number;
+  readonly clear_force_fields: () => void;
   readonly reset_grid: () => void;
+  readonly set_particles: (a: number) => void;
+  readonly clear_powerup_spawns: () => void;
   readonly set_speed: (a: number) => void;
-  readonly set_balls_per_team: (a: number) => void;
   readonly resize: (a: number, b: number) => void;
-  readonly set_num_balls: (a: number) => void;
+  readonly stop: () => void;
+  readonly clear_spawn_zones: () => void;
   readonly __wbindgen_exn_store: (a: number) => void;
   readonly __externref_table_alloc: () => number;
   readonly __wbindgen_export_2: WebAssembly.Table;
@@ -37,7 +303,8 @@ export interface InitOutput {
   readonly __wbindgen_realloc: (a: number, b: number, c: number, d: number) => number;
   readonly __wbindgen_export_5: WebAssembly.Table;
   readonly __externref_table_dealloc: (a: number) => void;
-  readonly wasm_bindgen__convert__closures_____invoke__h063f14237101987b: (a: number, b: number, c: number) => void;
+  readonly __wbindgen_free: (a: number, b: number, c: number) => void;
+  readonly wasm_bindgen__convert__closures_____invoke__hdfeb4b9504cb3af7: (a: number, b: number, c: number) => void;
   readonly __wbindgen_start: () => void;
 }
 
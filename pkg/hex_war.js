@@ -217,7 +217,44 @@ function takeFromExternrefTable0(idx) {
     return value;
 }
 /**
- * Initialize with *per-team* count (0..=5) and speed
+ * Fix the world at `width`×`height` world units, scaled and letterboxed into the canvas,
+ * so the board is the same on every screen; `0, 0` follows the canvas size again. Both
+ * sides must be 100..=10000. Restarts the match.
+ * @param {number} width
+ * @param {number} height
+ */
+export function set_world_size(width, height) {
+    const ret = wasm.set_world_size(width, height);
+    if (ret[1]) {
+        throw takeFromExternrefTable0(ret[0]);
+    }
+}
+
+/**
+ * Territory chart overlay. `x`/`y` are CSS px from the top-left corner; negative values
+ * anchor to the right/bottom edge. `opacity` is 0..=1.
+ * @param {boolean} enabled
+ * @param {number} x
+ * @param {number} y
+ * @param {number} width
+ * @param {number} height
+ * @param {number} opacity
+ */
+export function set_chart_overlay(enabled, x, y, width, height, opacity) {
+    wasm.set_chart_overlay(enabled, x, y, width, height, opacity);
+}
+
+/**
+ * Toroidal arena: balls leaving one edge reappear on the opposite one, and claiming,
+ * collisions and pickups reach across the seam. Turning it off pulls balls back inside.
+ * The hexes are laid out to tile across the seams, so either way the board restarts.
+ * @param {boolean} on
+ */
+export function set_wrap(on) {
+    wasm.set_wrap(on);
+}
+
+/**
  * @param {string} canvas_id
  * @param {number} css_w
  * @param {number} css_h
@@ -233,19 +270,145 @@ export function init_app(canvas_id, css_w, css_h, balls_per_team, speed) {
     }
 }
 
-export function start() {
-    const ret = wasm.start();
+/**
+ * Particle effects (impact sparks, capture chips, wall dust): 0 turns them off, 1 is the
+ * default amount, up to 3. Visual only.
+ * @param {number} intensity
+ */
+export function set_particles(intensity) {
+    wasm.set_particles(intensity);
+}
+
+/**
+ * Power-up spawn rules: on/off, seconds between spawns, max pickups on board, effect duration.
+ * @param {boolean} enabled
+ * @param {number} interval_secs
+ * @param {number} max_active
+ * @param {number} duration_secs
+ */
+export function set_powerups(enabled, interval_secs, max_active, duration_secs) {
+    wasm.set_powerups(enabled, interval_secs, max_active, duration_secs);
+}
+
+/**
+ * Place a force field centred on world point `(x, y)`. `kind` is
+ * "attract", "repel", "wind" (pushing along `angle_deg`, 0 = right, 90 = down) or
+ * "vortex" (clockwise; negative `strength` turns it anticlockwise). `strength` is the
+ * peak acceleration in px/s² (up to 5000), fading to nothing at `radius` (up to 20000)
+ * except for wind; a `radius` of 0 covers the whole arena. Fields turn balls without changing their speed.
+ * @param {string} kind
+ * @param {number} x
+ * @param {number} y
+ * @param {number} radius
+ * @param {number} strength
+ * @param {number} angle_deg
+ */
+export function add_force_field(kind, x, y, radius, strength, angle_deg) {
+    const ptr0 = passStringToWasm0(kind, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+    const len0 = WASM_VECTOR_LEN;
+    const ret = wasm.add_force_field(ptr0, len0, x, y, radius, strength, angle_deg);
     if (ret[1]) {
         throw takeFromExternrefTable0(ret[0]);
     }
 }
 
-export function stop() {
-    wasm.stop();
+/**
+ * How hexes animate when they change owner: "none", "fade", "pop" or "rotate", over
+ * `duration_secs` of sim time (0..=2). Visual only; scoring is unaffected.
+ * @param {string} style
+ * @param {number} duration_secs
+ */
+export function set_flip_animation(style, duration_secs) {
+    const ptr0 = passStringToWasm0(style, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+    const len0 = WASM_VECTOR_LEN;
+    const ret = wasm.set_flip_animation(ptr0, len0, duration_secs);
+    if (ret[1]) {
+        throw takeFromExternrefTable0(ret[0]);
+    }
 }
 
-export function reset_grid() {
-    wasm.reset_grid();
+let cachedFloat64ArrayMemory0 = null;
+
+function getFloat64ArrayMemory0() {
+    if (cachedFloat64ArrayMemory0 === null || cachedFloat64ArrayMemory0.byteLength === 0) {
+        cachedFloat64ArrayMemory0 = new Float64Array(wasm.memory.buffer);
+    }
+    return cachedFloat64ArrayMemory0;
+}
+
+function getArrayF64FromWasm0(ptr, len) {
+    ptr = ptr >>> 0;
+    return getFloat64ArrayMemory0().subarray(ptr / 8, ptr / 8 + len);
+}
+/**
+ * World coordinates `[x, y]` under canvas CSS pixel `(x, y)`, through the letterbox and camera.
+ * @param {number} x
+ * @param {number} y
+ * @returns {Float64Array}
+ */
+export function screen_to_world(x, y) {
+    const ret = wasm.screen_to_world(x, y);
+    var v1 = getArrayF64FromWasm0(ret[0], ret[1]).slice();
+    wasm.__wbindgen_free(ret[0], ret[1] * 8, 8);
+    return v1;
+}
+
+function getArrayU8FromWasm0(ptr, len) {
+    ptr = ptr >>> 0;
+    return getUint8ArrayMemory0().subarray(ptr / 1, ptr / 1 + len);
+}
+/**
+ * The current board rendered in software and encoded as PNG, `scale` device pixels per
+ * CSS pixel (clamped to 0.25..=4). Overlays and power-up glyphs are not included.
+ * @param {number} scale
+ * @returns {Uint8Array}
+ */
+export function export_png(scale) {
+    const ret = wasm.export_png(scale);
+    if (ret[3]) {
+        throw takeFromExternrefTable0(ret[2]);
+    }
+    var v1 = getArrayU8FromWasm0(ret[0], ret[1]).slice();
+    wasm.__wbindgen_free(ret[0], ret[1] * 1, 1);
+    return v1;
+}
+
+/**
+ * Keyboard steering for `team`'s player ball; `(x,y)` is a direction, clamped to unit length.
+ * @param {string} team
+ * @param {number} x
+ * @param {number} y
+ */
+export function player_steer(team, x, y) {
+    const ptr0 = passStringToWasm0(team, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+    const len0 = WASM_VECTOR_LEN;
+    const ret = wasm.player_steer(ptr0, len0, x, y);
+    if (ret[1]) {
+        throw takeFromExternrefTable0(ret[0]);
+    }
+}
+
+/**
+ * Mark `team`'s first ball as player-controlled (or release it back to the simulation).
+ * @param {string} team
+ * @param {boolean} enabled
+ */
+export function set_player_control(team, enabled) {
+    const ptr0 = passStringToWasm0(team, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+    const len0 = WASM_VECTOR_LEN;
+    const ret = wasm.set_player_control(ptr0, len0, enabled);
+    if (ret[1]) {
+        throw takeFromExternrefTable0(ret[0]);
+    }
+}
+
+/**
+ * Current world size in world units (the canvas size unless fixed with `set_world_size`).
+ * @returns {number}
+ */
+export function world_width() {
+    const ret = wasm.world_width();
+    return ret;
 }
 
 /**
@@ -255,20 +418,101 @@ export function set_speed(multiplier) {
     wasm.set_speed(multiplier);
 }
 
+export function clear_powerup_spawns() {
+    wasm.clear_powerup_spawns();
+}
+
 /**
- * Set balls *per team* (0..=5)
- * @param {number} n
+ * Round-robin tournament over headless matches. `entrants` is a comma-separated list of
+ * `controller` or `controller:heavy/light` specs (e.g. `"regions,chase:2/0,none"`); every
+ * pair plays each seed twice with sides swapped. `seeds` is capped at 20, `match_secs` at
+ * 300, entrants at 8 and the whole run at 20000 simulated seconds. Returns Elo ratings and
+ * a win matrix as `format` "csv" or "json".
+ * @param {string} entrants
+ * @param {number} seeds
+ * @param {number} match_secs
+ * @param {number} balls_per_team
+ * @param {string} format
+ * @returns {string}
  */
-export function set_balls_per_team(n) {
-    wasm.set_balls_per_team(n);
+export function run_tournament(entrants, seeds, match_secs, balls_per_team, format) {
+    let deferred4_0;
+    let deferred4_1;
+    try {
+        const ptr0 = passStringToWasm0(entrants, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ptr1 = passStringToWasm0(format, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len1 = WASM_VECTOR_LEN;
+        const ret = wasm.run_tournament(ptr0, len0, seeds, match_secs, balls_per_team, ptr1, len1);
+        var ptr3 = ret[0];
+        var len3 = ret[1];
+        if (ret[3]) {
+            ptr3 = 0; len3 = 0;
+            throw takeFromExternrefTable0(ret[2]);
+        }
+        deferred4_0 = ptr3;
+        deferred4_1 = len3;
+        return getStringFromWasm0(ptr3, len3);
+    } finally {
+        wasm.__wbindgen_free(deferred4_0, deferred4_1, 1);
+    }
 }
 
 /**
- * Back-compat alias (treat input as per-team)
- * @param {number} n
+ * Pan by a drag of `(dx, dy)` canvas CSS pixels.
+ * @param {number} dx
+ * @param {number} dy
  */
-export function set_num_balls(n) {
-    wasm.set_balls_per_team(n);
+export function camera_pan(dx, dy) {
+    wasm.camera_pan(dx, dy);
+}
+
+export function reset_grid() {
+    wasm.reset_grid();
+}
+
+/**
+ * Assign a built-in AI to `team`: "regions", "defend", "chase", "wander", or "none".
+ * @param {string} team
+ * @param {string} name
+ */
+export function set_team_controller(team, name) {
+    const ptr0 = passStringToWasm0(team, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+    const len0 = WASM_VECTOR_LEN;
+    const ptr1 = passStringToWasm0(name, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+    const len1 = WASM_VECTOR_LEN;
+    const ret = wasm.set_team_controller(ptr0, len0, ptr1, len1);
+    if (ret[1]) {
+        throw takeFromExternrefTable0(ret[0]);
+    }
+}
+
+/**
+ * Grid by hex radius in world units; 0 goes back to the automatic size for the world.
+ * Rebuilds the grid, respawns balls sized to the new hexes and restarts the match. A later
+ * resize that would take the grid past 250k cells goes back to the automatic size.
+ * @param {number} radius
+ */
+export function set_hex_radius(radius) {
+    const ret = wasm.set_hex_radius(radius);
+    if (ret[1]) {
+        throw takeFromExternrefTable0(ret[0]);
+    }
+}
+
+/**
+ * Per-team spawn mix: of each team's balls, `heavy` spawn big/slow and `light` small/fast.
+ * @param {string} team
+ * @param {number} heavy
+ * @param {number} light
+ */
+export function set_ball_mix(team, heavy, light) {
+    const ptr0 = passStringToWasm0(team, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+    const len0 = WASM_VECTOR_LEN;
+    const ret = wasm.set_ball_mix(ptr0, len0, heavy, light);
+    if (ret[1]) {
+        throw takeFromExternrefTable0(ret[0]);
+    }
 }
 
 /**
@@ -279,8 +523,440 @@ export function resize(css_w, css_h) {
     wasm.resize(css_w, css_h);
 }
 
-function __wbg_adapter_8(arg0, arg1, arg2) {
-    wasm.wasm_bindgen__convert__closures_____invoke__h063f14237101987b(arg0, arg1, arg2);
+export function clear_spawn_zones() {
+    wasm.clear_spawn_zones();
+}
+
+/**
+ * Switch colour theme by name (see `THEME_NAMES`); applies from the next frame, or at once
+ * while stopped. The match carries on untouched.
+ * @param {string} name
+ */
+export function set_theme(name) {
+    const ptr0 = passStringToWasm0(name, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+    const len0 = WASM_VECTOR_LEN;
+    const ret = wasm.set_theme(ptr0, len0);
+    if (ret[1]) {
+        throw takeFromExternrefTable0(ret[0]);
+    }
+}
+
+/**
+ * Enable or disable one kind: "speed", "grow", "paint_bomb", "shield" or "split".
+ * @param {string} kind
+ * @param {boolean} enabled
+ */
+export function set_powerup_kind(kind, enabled) {
+    const ptr0 = passStringToWasm0(kind, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+    const len0 = WASM_VECTOR_LEN;
+    const ret = wasm.set_powerup_kind(ptr0, len0, enabled);
+    if (ret[1]) {
+        throw takeFromExternrefTable0(ret[0]);
+    }
+}
+
+/**
+ * Back to the whole arena.
+ */
+export function camera_reset() {
+    wasm.camera_reset();
+}
+
+/**
+ * Remove the ball with `id`; returns false if there is none.
+ * @param {number} id
+ * @returns {boolean}
+ */
+export function remove_ball(id) {
+    const ret = wasm.remove_ball(id);
+    return ret !== 0;
+}
+
+/**
+ * Per-hex activity since the last reset:
+ * `{ cols, rows, flips, white_secs, black_secs, longest_owner }`, each matrix indexed `[row][col]`.
+ * @returns {any}
+ */
+export function get_heatmap() {
+    const ret = wasm.get_heatmap();
+    if (ret[2]) {
+        throw takeFromExternrefTable0(ret[1]);
+    }
+    return takeFromExternrefTable0(ret[0]);
+}
+
+/**
+ * Draw force fields as faint arrows. Visual only.
+ * @param {boolean} on
+ */
+export function set_show_fields(on) {
+    wasm.set_show_fields(on);
+}
+
+export function start() {
+    const ret = wasm.start();
+    if (ret[1]) {
+        throw takeFromExternrefTable0(ret[0]);
+    }
+}
+
+/**
+ * Grid by count: the largest hexes giving at least `cols` columns and `rows` rows (0 leaves
+ * that axis free), e.g. `300, 0` for a dense board or `15, 0` for a chunky one. Rebuilds
+ * like `set_hex_radius`.
+ * @param {number} cols
+ * @param {number} rows
+ */
+export function set_grid_size(cols, rows) {
+    const ret = wasm.set_grid_size(cols, rows);
+    if (ret[1]) {
+        throw takeFromExternrefTable0(ret[0]);
+    }
+}
+
+/**
+ * The current board as a standalone SVG document: hexes, glossy balls, and (optionally)
+ * a score caption. Uses the same geometry as the canvas renderer.
+ * @param {boolean} caption
+ * @returns {string}
+ */
+export function export_svg(caption) {
+    let deferred2_0;
+    let deferred2_1;
+    try {
+        const ret = wasm.export_svg(caption);
+        var ptr1 = ret[0];
+        var len1 = ret[1];
+        if (ret[3]) {
+            ptr1 = 0; len1 = 0;
+            throw takeFromExternrefTable0(ret[2]);
+        }
+        deferred2_0 = ptr1;
+        deferred2_1 = len1;
+        return getStringFromWasm0(ptr1, len1);
+    } finally {
+        wasm.__wbindgen_free(deferred2_0, deferred2_1, 1);
+    }
+}
+
+/**
+ * Per-team handicap for balance experiments: `extra_balls` (-5..=5) added to the balls
+ * per team, multipliers (0.25..=4) on spawn speed, ball radius and claim radius, and a
+ * cruising speed cap `max_speed` in px/s (50..=2000, 0 for the default). Balls are never
+ * spawned too big to fit the world, whatever the radius multiplier. Respawns balls;
+ * `set_handicap(team, 0, 1, 1, 1, 0)` clears it.
+ * @param {string} team
+ * @param {number} extra_balls
+ * @param {number} speed
+ * @param {number} radius
+ * @param {number} claim
+ * @param {number} max_speed
+ */
+export function set_handicap(team, extra_balls, speed, radius, claim, max_speed) {
+    const ptr0 = passStringToWasm0(team, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+    const len0 = WASM_VECTOR_LEN;
+    const ret = wasm.set_handicap(ptr0, len0, extra_balls, speed, radius, claim, max_speed);
+    if (ret[1]) {
+        throw takeFromExternrefTable0(ret[0]);
+    }
+}
+
+/**
+ * Fill hexes by "owner" (team colour) or "heat" (how often each hex flipped).
+ * @param {string} mode
+ */
+export function set_cell_view(mode) {
+    const ptr0 = passStringToWasm0(mode, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+    const len0 = WASM_VECTOR_LEN;
+    const ret = wasm.set_cell_view(ptr0, len0);
+    if (ret[1]) {
+        throw takeFromExternrefTable0(ret[0]);
+    }
+}
+
+export function clear_force_fields() {
+    wasm.clear_force_fields();
+}
+
+/**
+ * Headless fair match: `seed` (0 picks one) played twice between two entrant specs as in
+ * `run_tournament`, `first` taking white in leg one and black in leg two, so both get each
+ * colour, side and spawn. Legs last at most 300 sim seconds. `layout` as in `set_spawn_layout`.
+ * Returns the `get_fair_match` JSON.
+ * @param {string} first
+ * @param {string} second
+ * @param {number} seed
+ * @param {number} match_secs
+ * @param {number} balls_per_team
+ * @param {string} layout
+ * @returns {string}
+ */
+export function run_fair_match(first, second, seed, match_secs, balls_per_team, layout) {
+    let deferred5_0;
+    let deferred5_1;
+    try {
+        const ptr0 = passStringToWasm0(first, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ptr1 = passStringToWasm0(second, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len1 = WASM_VECTOR_LEN;
+        const ptr2 = passStringToWasm0(layout, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len2 = WASM_VECTOR_LEN;
+        const ret = wasm.run_fair_match(ptr0, len0, ptr1, len1, seed, match_secs, balls_per_team, ptr2, len2);
+        var ptr4 = ret[0];
+        var len4 = ret[1];
+        if (ret[3]) {
+            ptr4 = 0; len4 = 0;
+            throw takeFromExternrefTable0(ret[2]);
+        }
+        deferred5_0 = ptr4;
+        deferred5_1 = len4;
+        return getStringFromWasm0(ptr4, len4);
+    } finally {
+        wasm.__wbindgen_free(deferred5_0, deferred5_1, 1);
+    }
+}
+
+/**
+ * @param {number} n
+ */
+export function set_num_balls(n) {
+    wasm.set_balls_per_team(n);
+}
+
+/**
+ * Override the current theme's ball trails: `kind` is "off", "line" or "ghosts", `length`
+ * the number of frames kept (up to 48), `opacity` at the ball end. `set_theme` restores
+ * the theme's own trails.
+ * @param {string} kind
+ * @param {number} length
+ * @param {number} opacity
+ */
+export function set_trails(kind, length, opacity) {
+    const ptr0 = passStringToWasm0(kind, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+    const len0 = WASM_VECTOR_LEN;
+    const ret = wasm.set_trails(ptr0, len0, length, opacity);
+    if (ret[1]) {
+        throw takeFromExternrefTable0(ret[0]);
+    }
+}
+
+/**
+ * Play the current setup as a two-leg fair match of `leg_secs` sim seconds per leg. Both
+ * legs start from `seed` (0 picks one); for the second, the teams' controllers, ball mixes
+ * and player control swap colours, so each side plays both spawns. Starts the loop; it
+ * stops after the second leg. Poll `get_fair_match` for progress and the verdict.
+ * @param {number} leg_secs
+ * @param {number} seed
+ */
+export function start_fair_match(leg_secs, seed) {
+    const ret = wasm.start_fair_match(leg_secs, seed);
+    if (ret[1]) {
+        throw takeFromExternrefTable0(ret[0]);
+    }
+}
+
+/**
+ * Match statistics since the last reset:
+ * `{ time, teams: { white, black }, balls: [...], series: { interval, t, white, black, points_white, points_black } }`.
+ * Per ball: id, cells flipped/stolen, distance, collisions, wall and territory bounces, peak speed.
+ * @returns {any}
+ */
+export function get_stats() {
+    const ret = wasm.get_stats();
+    if (ret[2]) {
+        throw takeFromExternrefTable0(ret[1]);
+    }
+    return takeFromExternrefTable0(ret[0]);
+}
+
+/**
+ * What `edge` ("left", "right", "top", "bottom" or "all") does to balls that hit it:
+ * - "elastic": perfect mirror
+ * - "damped": mirror keeping `amount` (0..=1) of the ball's cruising speed
+ * - "scatter": mirror turned by a random angle of up to `amount` degrees (0..=80)
+ * - "sticky": holds the ball for `amount` seconds (0..=5), then releases it mirrored
+ * - "white" / "black": owned by that team, whose balls pass through and come back on the
+ *   opposite edge while the enemy bounces
+ *
+ * Walls are ignored while wrap-around is on.
+ * @param {string} edge
+ * @param {string} kind
+ * @param {number} amount
+ */
+export function set_wall(edge, kind, amount) {
+    const ptr0 = passStringToWasm0(edge, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+    const len0 = WASM_VECTOR_LEN;
+    const ptr1 = passStringToWasm0(kind, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+    const len1 = WASM_VECTOR_LEN;
+    const ret = wasm.set_wall(ptr0, len0, ptr1, len1, amount);
+    if (ret[1]) {
+        throw takeFromExternrefTable0(ret[0]);
+    }
+}
+
+/**
+ * Where balls start: "sides" (random in each team's quarter), "mirrored" (black is the
+ * point reflection of white), "line" or "wedge" formations, "zones" (inside the areas
+ * from `add_spawn_zone`) or "random" (anywhere). Respawns balls.
+ * @param {string} name
+ */
+export function set_spawn_layout(name) {
+    const ptr0 = passStringToWasm0(name, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+    const len0 = WASM_VECTOR_LEN;
+    const ret = wasm.set_spawn_layout(ptr0, len0);
+    if (ret[1]) {
+        throw takeFromExternrefTable0(ret[0]);
+    }
+}
+
+/**
+ * Zoom by `factor` (1 = no change) about canvas CSS pixel `(x, y)`; zoom stays in 1..=8.
+ * @param {number} x
+ * @param {number} y
+ * @param {number} factor
+ */
+export function camera_zoom_at(x, y, factor) {
+    wasm.camera_zoom_at(x, y, factor);
+}
+
+/**
+ * Fair match progress: `{ seed, leg, leg_secs, elapsed, swapped, legs: [{ white, black }],
+ * first, second, verdict }`. `leg` is 1 or 2 while playing and 0 once done; `swapped` is
+ * true while the sides hold each other's colours, so player input for the side that
+ * started as white goes to "black" and vice versa; `first` and `second` are combined
+ * points of the side that started as white and black; `verdict` is "first", "second" or
+ * "draw" when done, otherwise null. Null if no fair match was started.
+ * @returns {any}
+ */
+export function get_fair_match() {
+    const ret = wasm.get_fair_match();
+    if (ret[2]) {
+        throw takeFromExternrefTable0(ret[1]);
+    }
+    return takeFromExternrefTable0(ret[0]);
+}
+
+export function stop() {
+    wasm.stop();
+}
+
+/**
+ * @param {number} n
+ */
+export function set_balls_per_team(n) {
+    wasm.set_balls_per_team(n);
+}
+
+/**
+ * @param {string} team
+ */
+export function clear_player_target(team) {
+    const ptr0 = passStringToWasm0(team, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+    const len0 = WASM_VECTOR_LEN;
+    const ret = wasm.clear_player_target(ptr0, len0);
+    if (ret[1]) {
+        throw takeFromExternrefTable0(ret[0]);
+    }
+}
+
+/**
+ * Reduced-motion mode: no trails, particles or flip animations (hexes change instantly).
+ * The page sets this from `prefers-reduced-motion`.
+ * @param {boolean} on
+ */
+export function set_reduced_motion(on) {
+    wasm.set_reduced_motion(on);
+}
+
+/**
+ * Pointer steering: `team`'s player ball turns toward the world point under canvas CSS
+ * pixel `(x,y)`.
+ * @param {string} team
+ * @param {number} x
+ * @param {number} y
+ */
+export function player_target(team, x, y) {
+    const ptr0 = passStringToWasm0(team, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+    const len0 = WASM_VECTOR_LEN;
+    const ret = wasm.player_target(ptr0, len0, x, y);
+    if (ret[1]) {
+        throw takeFromExternrefTable0(ret[0]);
+    }
+}
+
+/**
+ * Pin power-up spawns to a hex; once any are set, pickups only appear on these.
+ * @param {number} col
+ * @param {number} row
+ */
+export function add_powerup_spawn(col, row) {
+    wasm.add_powerup_spawn(col, row);
+}
+
+/**
+ * Add a spawn area for `team` in world units, used by the "zones" layout. A team without
+ * zones spawns as in "sides". Takes effect at the next respawn.
+ * @param {string} team
+ * @param {number} x
+ * @param {number} y
+ * @param {number} width
+ * @param {number} height
+ */
+export function add_spawn_zone(team, x, y, width, height) {
+    const ptr0 = passStringToWasm0(team, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+    const len0 = WASM_VECTOR_LEN;
+    const ret = wasm.add_spawn_zone(ptr0, len0, x, y, width, height);
+    if (ret[1]) {
+        throw takeFromExternrefTable0(ret[0]);
+    }
+}
+
+/**
+ * @returns {number}
+ */
+export function world_height() {
+    const ret = wasm.world_height();
+    return ret;
+}
+
+/**
+ * Id of the topmost ball covering world point `(x, y)`, if any.
+ * @param {number} x
+ * @param {number} y
+ * @returns {number | undefined}
+ */
+export function ball_at(x, y) {
+    const ret = wasm.ball_at(x, y);
+    return ret === 0x100000001 ? undefined : ret;
+}
+
+/**
+ * Drop a `team` ball at world point `(x, y)` with velocity `(vx, vy)` px/s and `radius`
+ * (up to 60) into the running match, leaving the other balls and the grid alone. A zero
+ * velocity picks a random heading at the standard speed; a zero radius the standard
+ * size. Radii too big for the world are shrunk to fit, and speeds under 60 px/s raised to
+ * that. Returns the ball's id, which stays valid until the ball is removed or the team is
+ * respawned.
+ * @param {string} team
+ * @param {number} x
+ * @param {number} y
+ * @param {number} vx
+ * @param {number} vy
+ * @param {number} radius
+ * @returns {number}
+ */
+export function add_ball(team, x, y, vx, vy, radius) {
+    const ptr0 = passStringToWasm0(team, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+    const len0 = WASM_VECTOR_LEN;
+    const ret = wasm.add_ball(ptr0, len0, x, y, vx, vy, radius);
+    if (ret[2]) {
+        throw takeFromExternrefTable0(ret[1]);
+    }
+    return ret[0] >>> 0;
+}
+
+function __wbg_adapter_10(arg0, arg1, arg2) {
+    wasm.wasm_bindgen__convert__closures_____invoke__hdfeb4b9504cb3af7(arg0, arg1, arg2);
 }
 
 const EXPECTED_RESPONSE_TYPES = new Set(['basic', 'cors', 'default']);
@@ -352,6 +1028,9 @@ function __wbg_get_imports() {
     imports.wbg.__wbg_fillRect_a160edfa11fce49b = function(arg0, arg1, arg2, arg3, arg4) {
         arg0.fillRect(arg1, arg2, arg3, arg4);
     };
+    imports.wbg.__wbg_fillText_c105710356b625aa = function() { return handleError(function (arg0, arg1, arg2, arg3, arg4) {
+        arg0.fillText(getStringFromWasm0(arg1, arg2), arg3, arg4);
+    }, arguments) };
     imports.wbg.__wbg_fill_7b331ac62ac7c50b = function(arg0) {
         arg0.fill();
     };
@@ -399,6 +1078,10 @@ function __wbg_get_imports() {
     imports.wbg.__wbg_moveTo_a0b1ec729ba8ee5d = function(arg0, arg1, arg2) {
         arg0.moveTo(arg1, arg2);
     };
+    imports.wbg.__wbg_new_1f3a344cf3123716 = function() {
+        const ret = new Array();
+        return ret;
+    };
     imports.wbg.__wbg_newnoargs_254190557c45b4ec = function(arg0, arg1) {
         const ret = new Function(getStringFromWasm0(arg0, arg1));
         return ret;
@@ -407,6 +1090,14 @@ function __wbg_get_imports() {
         const ret = arg0.now();
         return ret;
     };
+    imports.wbg.__wbg_of_d0e190785e1ebbb6 = function(arg0, arg1) {
+        const ret = Array.of(arg0, arg1);
+        return ret;
+    };
+    imports.wbg.__wbg_parse_442f5ba02e5eaf8b = function() { return handleError(function (arg0, arg1) {
+        const ret = JSON.parse(getStringFromWasm0(arg0, arg1));
+        return ret;
+    }, arguments) };
     imports.wbg.__wbg_performance_a221af8decc752fb = function(arg0) {
         const ret = arg0.performance;
         return isLikeNone(ret) ? 0 : addToExternrefTable0(ret);
@@ -419,12 +1110,21 @@ function __wbg_get_imports() {
         const ret = arg0.requestAnimationFrame(arg1);
         return ret;
     }, arguments) };
+    imports.wbg.__wbg_setLineDash_5c96338d7fd195f9 = function() { return handleError(function (arg0, arg1) {
+        arg0.setLineDash(arg1);
+    }, arguments) };
     imports.wbg.__wbg_setTransform_e8b3bc7c8210c0e6 = function() { return handleError(function (arg0, arg1, arg2, arg3, arg4, arg5, arg6) {
         arg0.setTransform(arg1, arg2, arg3, arg4, arg5, arg6);
     }, arguments) };
-    imports.wbg.__wbg_setfillStyle_9d745b4440df0b8b = function(arg0, arg1) {
+    imports.wbg.__wbg_setfillStyle_5242e4485d022084 = function(arg0, arg1) {
         arg0.fillStyle = arg1;
     };
+    imports.wbg.__wbg_setfillStyle_a9ad5b25cf62a5bc = function(arg0, arg1, arg2) {
+        arg0.fillStyle = getStringFromWasm0(arg1, arg2);
+    };
+    imports.wbg.__wbg_setfont_175a33e591a4080a = function(arg0, arg1, arg2) {
+        arg0.font = getStringFromWasm0(arg1, arg2);
+    };
     imports.wbg.__wbg_setglobalAlpha_3775d2bf14be6337 = function(arg0, arg1) {
         arg0.globalAlpha = arg1;
     };
@@ -434,14 +1134,23 @@ function __wbg_get_imports() {
     imports.wbg.__wbg_setinnerHTML_34e240d6b8e8260c = function(arg0, arg1, arg2) {
         arg0.innerHTML = getStringFromWasm0(arg1, arg2);
     };
+    imports.wbg.__wbg_setlineCap_c4746ffc7c654931 = function(arg0, arg1, arg2) {
+        arg0.lineCap = getStringFromWasm0(arg1, arg2);
+    };
     imports.wbg.__wbg_setlineJoin_30c59321fbc0bd3d = function(arg0, arg1, arg2) {
         arg0.lineJoin = getStringFromWasm0(arg1, arg2);
     };
     imports.wbg.__wbg_setlineWidth_069d571345379833 = function(arg0, arg1) {
         arg0.lineWidth = arg1;
     };
-    imports.wbg.__wbg_setstrokeStyle_7f1d68ee3f91420a = function(arg0, arg1) {
-        arg0.strokeStyle = arg1;
+    imports.wbg.__wbg_setstrokeStyle_3c450999cfcdcd2f = function(arg0, arg1, arg2) {
+        arg0.strokeStyle = getStringFromWasm0(arg1, arg2);
+    };
+    imports.wbg.__wbg_settextAlign_45538ab0baa2edad = function(arg0, arg1, arg2) {
+        arg0.textAlign = getStringFromWasm0(arg1, arg2);
+    };
+    imports.wbg.__wbg_settextBaseline_8af6d434952d07cc = function(arg0, arg1, arg2) {
+        arg0.textBaseline = getStringFromWasm0(arg1, arg2);
     };
     imports.wbg.__wbg_setwidth_40a6ed203b92839d = function(arg0, arg1) {
         arg0.width = arg1 >>> 0;
@@ -493,9 +1202,14 @@ function __wbg_get_imports() {
         const ret = getStringFromWasm0(arg0, arg1);
         return ret;
     };
-    imports.wbg.__wbindgen_cast_f1444b50dd515a58 = function(arg0, arg1) {
-        // Cast intrinsic for `Closure(Closure { dtor_idx: 12, function: Function { arguments: [F64], shim_idx: 13, ret: Unit, inner_ret: Some(Unit) }, mutable: true }) -> Externref`.
-        const ret = makeMutClosure(arg0, arg1, 12, __wbg_adapter_8);
+    imports.wbg.__wbindgen_cast_d6cd19b81560fd6e = function(arg0) {
+        // Cast intrinsic for `F64 -> Externref`.
+        const ret = arg0;
+        return ret;
+    };
+    imports.wbg.__wbindgen_cast_e27ccbe43a046630 = function(arg0, arg1) {
+        // Cast intrinsic for `Closure(Closure { dtor_idx: 55, function: Function { arguments: [F64], shim_idx: 56, ret: Unit, inner_ret: Some(Unit) }, mutable: true }) -> Externref`.
+        const ret = makeMutClosure(arg0, arg1, 55, __wbg_adapter_10);
         return ret;
     };
     imports.wbg.__wbindgen_init_externref_table = function() {
@@ -520,6 +1234,7 @@ function __wbg_finalize_init(instance, module) {
     wasm = instance.exports;
     __wbg_init.__wbindgen_wasm_module = module;
     cachedDataViewMemory0 = null;
+    cachedFloat64ArrayMemory0 = null;
     cachedUint8ArrayMemory0 = null;
 
 
@@ -0,0 +1,67 @@
+/* tslint:disable */
+/* eslint-disable */
+export const memory: WebAssembly.Memory;
+export const add_ball: (a: number, b: number, c: number, d: number, e: number, f: number, g: number) => [number, number, number];
+export const add_force_field: (a: number, b: number, c: number, d: number, e: number, f: number, g: number) => [number, number];
+export const add_spawn_zone: (a: number, b: number, c: number, d: number, e: number, f: number) => [number, number];
+export const ball_at: (a: number, b: number) => number;
+export const camera_pan: (a: number, b: number) => void;
+export const camera_zoom_at: (a: number, b: number, c: number) => void;
+export const clear_player_target: (a: number, b: number) => [number, number];
+export const export_png: (a: number) => [number, number, number, number];
+export const export_svg: (a: number) => [number, number, number, number];
+export const get_fair_match: () => [number, number, number];
+export const get_heatmap: () => [number, number, number];
+export const get_stats: () => [number, number, number];
+export const init_app: (a: number, b: number, c: number, d: number, e: number, f: number) => [number, number];
+export const player_steer: (a: number, b: number, c: number, d: number) => [number, number];
+export const player_target: (a: number, b: number, c: number, d: number) => [number, number];
+export const remove_ball: (a: number) => number;
+export const run_fair_match: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: number) => [number, number, number, number];
+export const run_tournament: (a: number, b: number, c: number, d: number, e: number, f: number, g: number) => [number, number, number, number];
+export const screen_to_world: (a: number, b: number) => [number, number];
+export const set_ball_mix: (a: number, b: number, c: number, d: number) => [number, number];
+export const set_cell_view: (a: number, b: number) => [number, number];
+export const set_chart_overlay: (a: number, b: number, c: number, d: number, e: number, f: number) => void;
+export const set_flip_animation: (a: number, b: number, c: number) => [number, number];
+export const set_grid_size: (a: number, b: number) => [number, number];
+export const set_handicap: (a: number, b: number, c: number, d: number, e: number, f: number, g: number) => [number, number];
+export const set_hex_radius: (a: number) => [number, number];
+export const set_player_control: (a: number, b: number, c: number) => [number, number];
+export const set_powerup_kind: (a: number, b: number, c: number) => [number, number];
+export const set_powerups: (a: number, b: number, c: number, d: number) => void;
+export const set_reduced_motion: (a: number) => void;
+export const set_show_fields: (a: number) => void;
+export const set_spawn_layout: (a: number, b: number) => [number, number];
+export const set_team_controller: (a: number, b: number, c: number, d: number) => [number, number];
+export const set_theme: (a: number, b: number) => [number, number];
+export const set_trails: (a: number, b: number, c: number, d: number) => [number, number];
+export const set_wall: (a: number, b: number, c: number, d: number, e: number) => [number, number];
+export const set_world_size: (a: number, b: number) => [number, number];
+export const set_wrap: (a: number) => void;
+export const start: () => [number, number];
+export const start_fair_match: (a: number, b: number) => [number, number];
+export const set_balls_per_team: (a: number) => void;
+export const set_num_balls: (a: number) => void;
+export const camera_reset: () => void;
+export const add_powerup_spawn: (a: number, b: number) => void;
+export const world_height: () => number;
+export const world_width: () => number;
+export const clear_force_fields: () => void;
+export const reset_grid: () => void;
+export const set_particles: (a: number) => void;
+export const clear_powerup_spawns: () => void;
+export const set_speed: (a: number) => void;
+export const resize: (a: number, b: number) => void;
+export const stop: () => void;
+export const clear_spawn_zones: () => void;
+export const __wbindgen_exn_store: (a: number) => void;
+export const __externref_table_alloc: () => number;
+export const __wbindgen_export_2: WebAssembly.Table;
+export const __wbindgen_malloc: (a: number, b: number) => number;
+export const __wbindgen_realloc: (a: number, b: number, c: number, d: number) => number;
+export const __wbindgen_export_5: WebAssembly.Table;
+export const __externref_table_dealloc: (a: number) => void;
+export const __wbindgen_free: (a: number, b: number, c: number) => void;
+export const wasm_bindgen__convert__closures_____invoke__hdfeb4b9504cb3af7: (a: number, b: number, c: number) => void;
+export const __wbindgen_start: () => void;
@@ -1,20 +0,0 @@
-/* tslint:disable */
-/* eslint-disable */
-export const memory: WebAssembly.Memory;
-export const init_app: (a: number, b: number, c: number, d: number, e: number, f: number) => [number, number];
-export const start: () => [number, number];
-export const stop: () => void;
-export const reset_grid: () => void;
-export const set_speed: (a: number) => void;
-export const set_balls_per_team: (a: number) => void;
-export const resize: (a: number, b: number) => void;
-export const set_num_balls: (a: number) => void;
-export const __wbindgen_exn_store: (a: number) => void;
-export const __externref_table_alloc: () => number;
-export const __wbindgen_export_2: WebAssembly.Table;
-export const __wbindgen_malloc: (a: number, b: number) => number;
-export const __wbindgen_realloc: (a: number, b: number, c: number, d: number) => number;
-export const __wbindgen_export_5: WebAssembly.Table;
-export const __externref_table_dealloc: (a: number) => void;
-export const wasm_bindgen__convert__closures_____invoke__h063f14237101987b: (a: number, b: number, c: number) => void;
-export const __wbindgen_start: () => void;
@@ -0,0 +1,133 @@
+use std::collections::HashMap;
+use std::f64::consts::PI;
+
+use crate::sim::{wrap_delta, Ball, Grid, HexColor, Rng, Team};
+
+/// Read-only view of the arena handed to controllers each tick.
+pub(crate) struct ArenaView<'a> {
+    pub grid: &'a Grid,
+    pub balls: &'a [Ball],
+    pub width: f64,
+    pub height: f64,
+    pub period: Option<(f64, f64)>, // set when the edges wrap, as for `wrap_delta`
+}
+
+impl ArenaView<'_> {
+    /// From `ball` to `(x, y)`, the short way round when the edges wrap.
+    fn delta(&self, ball: &Ball, x: f64, y: f64) -> (f64, f64) {
+        wrap_delta(x - ball.x, y - ball.y, self.period)
+    }
+
+    fn dist2(&self, ball: &Ball, x: f64, y: f64) -> f64 {
+        let (dx, dy) = self.delta(ball, x, y);
+        dx * dx + dy * dy
+    }
+
+    /// Unit vector from `ball` toward `(x, y)`.
+    fn toward(&self, ball: &Ball, x: f64, y: f64) -> (f64, f64) {
+        let (dx, dy) = self.delta(ball, x, y);
+        let len = (dx * dx + dy * dy).sqrt();
+        if len < 1e-6 { (0.0, 0.0) } else { (dx / len, dy / len) }
+    }
+}
+
+/// Steers the balls of one team. `steer` returns a desired acceleration direction for
+/// `view.balls[i]`; its length is clamped to 1 and scaled by `STEER_ACCEL` by the caller.
+/// Randomness must come from `rng` so seeded matches replay identically.
+pub(crate) trait BallController {
+    fn steer(&mut self, view: &ArenaView, i: usize, rng: &mut Rng) -> (f64, f64);
+}
+
+/// Built-in strategies, selectable by name from the wasm API.
+pub(crate) fn controller_by_name(name: &str) -> Option<Box<dyn BallController>> {
+    match name.to_ascii_lowercase().as_str() {
+        "regions" | "nearest_region" => Some(Box::new(NearestEnemyRegion)),
+        "defend" | "defend_border" => Some(Box::new(DefendBorder)),
+        "chase" => Some(Box::new(ChaseEnemy)),
+        "wander" => Some(Box::new(Wander::default())),
+        _ => None,
+    }
+}
+
+fn enemy_color(team: Team) -> HexColor {
+    match team { Team::White => HexColor::Black, Team::Black => HexColor::White }
+}
+
+/// Heads for the closest hex the other team owns.
+pub(crate) struct NearestEnemyRegion;
+
+impl BallController for NearestEnemyRegion {
+    fn steer(&mut self, view: &ArenaView, i: usize, _rng: &mut Rng) -> (f64, f64) {
+        let b = &view.balls[i];
+        let enemy = enemy_color(b.team);
+        let best = view.grid.cells.iter()
+            .filter(|c| c.color == enemy)
+            .min_by(|a, c| view.dist2(b, a.cx, a.cy).total_cmp(&view.dist2(b, c.cx, c.cy)));
+        match best {
+            Some(c) => view.toward(b, c.cx, c.cy),
+            None => (0.0, 0.0),
+        }
+    }
+}
+
+/// Retakes enemy hexes on the home half; with none left, patrols just inside the midline.
+pub(crate) struct DefendBorder;
+
+impl BallController for DefendBorder {
+    fn steer(&mut self, view: &ArenaView, i: usize, _rng: &mut Rng) -> (f64, f64) {
+        let b = &view.balls[i];
+        let mid = view.width * 0.5;
+        let home = |x: f64| match b.team { Team::White => x < mid, Team::Black => x >= mid };
+        let enemy = enemy_color(b.team);
+        let intruder = view.grid.cells.iter()
+            .filter(|c| c.color == enemy && home(c.cx))
+            .min_by(|a, c| view.dist2(b, a.cx, a.cy).total_cmp(&view.dist2(b, c.cx, c.cy)));
+        if let Some(c) = intruder { return view.toward(b, c.cx, c.cy); }
+
+        let guard_x = match b.team { Team::White => mid - view.width * 0.08, Team::Black => mid + view.width * 0.08 };
+        let (dx, _) = view.toward(b, guard_x, b.y);
+        (dx, 0.0)
+    }
+}
+
+/// Goes after the nearest enemy ball, leading it by a fraction of a second.
+pub(crate) struct ChaseEnemy;
+
+impl BallController for ChaseEnemy {
+    fn steer(&mut self, view: &ArenaView, i: usize, _rng: &mut Rng) -> (f64, f64) {
+        const LEAD_SECS: f64 = 0.25;
+        let b = &view.balls[i];
+        let prey = view.balls.iter()
+            .filter(|o| o.team != b.team)
+            .min_by(|a, c| view.dist2(b, a.x, a.y).total_cmp(&view.dist2(b, c.x, c.y)));
+        match prey {
+            Some(o) => view.toward(b, o.x + o.vx * LEAD_SECS, o.y + o.vy * LEAD_SECS),
+            None => (0.0, 0.0),
+        }
+    }
+}
+
+/// Drifting random heading per ball, nudged away from the walls unless the edges wrap.
+#[derive(Default)]
+pub(crate) struct Wander {
+    headings: HashMap<u32, f64>, // by `Ball::id`, so adding or removing balls keeps the rest
+}
+
+impl BallController for Wander {
+    fn steer(&mut self, view: &ArenaView, i: usize, rng: &mut Rng) -> (f64, f64) {
+        if self.headings.len() > view.balls.len() * 2 {
+            self.headings.retain(|id, _| view.balls.iter().any(|b| b.id == *id));
+        }
+        let b = &view.balls[i];
+        let h = self.headings.entry(b.id).or_insert_with(|| rng.range(0.0, 2.0 * PI));
+        *h += rng.range(-0.3, 0.3);
+        let (mx, my) = (view.width * 0.1, view.height * 0.1);
+        let (push_x, push_y) = if view.period.is_some() {
+            (0.0, 0.0) // no walls to avoid
+        } else {
+            (if b.x < mx { 0.5 } else if b.x > view.width - mx { -0.5 } else { 0.0 },
+             if b.y < my { 0.5 } else if b.y > view.height - my { -0.5 } else { 0.0 })
+        };
+        (h.cos() * 0.6 + push_x, h.sin() * 0.6 + push_y)
+    }
+}
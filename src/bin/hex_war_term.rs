@@ -0,0 +1,8 @@
+//! Watch matches in a terminal: `cargo run --release --features term --bin hex_war_term`.
+
+fn main() {
+    if let Err(e) = hex_war::term::run(std::env::args().skip(1)) {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,71 @@
+pub(crate) const MAX_ZOOM: f64 = 8.0;
+
+/// Zoom and pan over the arena. At `zoom` 1 the whole arena fills the view; `(x, y)` is
+/// the world point at the view's top-left corner. Screen points are world units from the
+/// arena's corner at zoom 1; `Letterbox::world_units` gets them from canvas CSS pixels.
+#[derive(Clone, Copy)]
+pub(crate) struct Camera {
+    pub zoom: f64,
+    pub x: f64,
+    pub y: f64,
+}
+
+impl Default for Camera {
+    fn default() -> Self { Camera { zoom: 1.0, x: 0.0, y: 0.0 } }
+}
+
+impl Camera {
+    pub fn screen_to_world(&self, sx: f64, sy: f64) -> (f64, f64) {
+        (sx / self.zoom + self.x, sy / self.zoom + self.y)
+    }
+
+    /// Multiply the zoom by `factor`, keeping the world point under `(sx, sy)` fixed.
+    pub fn zoom_at(&mut self, sx: f64, sy: f64, factor: f64, world_w: f64, world_h: f64) {
+        if !factor.is_finite() || factor <= 0.0 { return; }
+        let (wx, wy) = self.screen_to_world(sx, sy);
+        self.zoom = (self.zoom * factor).clamp(1.0, MAX_ZOOM);
+        self.x = wx - sx / self.zoom;
+        self.y = wy - sy / self.zoom;
+        self.clamp(world_w, world_h);
+    }
+
+    /// Drag the view by a screen-space delta.
+    pub fn pan(&mut self, dx: f64, dy: f64, world_w: f64, world_h: f64) {
+        self.x -= dx / self.zoom;
+        self.y -= dy / self.zoom;
+        self.clamp(world_w, world_h);
+    }
+
+    /// Keep the view inside the arena.
+    pub fn clamp(&mut self, world_w: f64, world_h: f64) {
+        self.x = self.x.clamp(0.0, (world_w - world_w / self.zoom).max(0.0));
+        self.y = self.y.clamp(0.0, (world_h - world_h / self.zoom).max(0.0));
+    }
+}
+
+/// Uniform scale and centring offset that fit a world into a canvas, leaving bars on the
+/// sides that don't match its aspect. Identity when the world is the canvas size.
+#[derive(Clone, Copy)]
+pub(crate) struct Letterbox {
+    pub scale: f64,           // CSS px per world unit
+    pub ox: f64, pub oy: f64, // CSS px from the canvas corner to the world's
+}
+
+impl Letterbox {
+    pub fn fit(view_w: f64, view_h: f64, world_w: f64, world_h: f64) -> Letterbox {
+        let scale = (view_w / world_w).min(view_h / world_h);
+        let scale = if scale.is_finite() && scale > 0.0 { scale } else { 1.0 };
+        Letterbox { scale, ox: (view_w - world_w * scale) * 0.5, oy: (view_h - world_h * scale) * 0.5 }
+    }
+
+    /// Canvas CSS px to the world units the camera works in.
+    pub fn world_units(&self, sx: f64, sy: f64) -> (f64, f64) {
+        ((sx - self.ox) / self.scale, (sy - self.oy) / self.scale)
+    }
+
+    /// Renderer view `(zoom, x, y)` for `camera` seen through this letterbox.
+    pub fn view(&self, camera: &Camera) -> (f64, f64, f64) {
+        let k = self.scale * camera.zoom;
+        (k, camera.x - self.ox / k, camera.y - self.oy / k)
+    }
+}
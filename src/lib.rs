@@ -1,5 +1,4 @@
 use std::cell::RefCell;
-use std::f64::consts::PI;
 use std::rc::Rc;
 
 use js_sys::Math;
@@ -7,49 +6,29 @@ use wasm_bindgen::prelude::*;
 use wasm_bindgen::JsCast;
 use web_sys::{window, CanvasRenderingContext2d, HtmlCanvasElement, Element};
 
-#[derive(Clone, Copy, PartialEq, Eq)]
-enum Team { Black, White }
-
-#[derive(Clone, Copy)]
-struct Ball {
-    x: f64, y: f64,
-    vx: f64, vy: f64,
-    team: Team,
-    radius: f64,
-    base_speed: f64,
-    last_bounce_ts: f64,
-}
-
-#[derive(Clone, Copy, PartialEq, Eq)]
-enum HexColor { Black, White }
-
-struct Cell {
-    col: usize, row: usize,
-    cx: f64, cy: f64,
-    color: HexColor,
-}
-
-struct Grid {
-    cells: Vec<Cell>,
-    cols: usize,
-    rows: usize,
-    r: f64,
-    hex_h: f64, // vertical step (flat-top)
-}
-
-impl Ball {
-    fn maintain_speed(&mut self) {
-        let mag = (self.vx * self.vx + self.vy * self.vy).sqrt();
-        if mag > 1e-6 {
-            let scale = self.base_speed / mag;
-            self.vx *= scale;
-            self.vy *= scale;
-        }
-    }
-}
-
-const TEAM_BOOST: f64 = 1.12;
-const MAX_BASE_SPEED: f64 = 520.0;
+mod ai;
+mod camera;
+mod json;
+mod particles;
+mod raster;
+mod render;
+mod sim;
+mod stats;
+mod svg;
+mod theme;
+#[cfg(feature = "term")]
+pub mod term;
+mod tournament;
+mod trails;
+
+use camera::{Camera, Letterbox};
+use particles::Particles;
+use render::{CanvasRenderer, CellView, FlipAnim, FlipStyle, Renderer, Rgba, TextAnchor};
+use sim::{FieldKind, ForceField, GridResolution, Handicap, PowerUpKind, Rng, Sim, SpawnLayout, Team, WallKind, EDGE_NAMES, MAX_BASE_SPEED};
+use theme::{Theme, TrailKind, TrailStyle, THEME_NAMES};
+use trails::Trails;
+use stats::Sample;
+use tournament::MatchResult;
 
 struct App {
     canvas: HtmlCanvasElement,
@@ -57,166 +36,73 @@ struct App {
     dpr: f64,
     css_w: f64, css_h: f64,
 
-    grid: Grid,
-    balls: Vec<Ball>,
+    sim: Sim,
 
     running: bool,
     last_ts: f64,
     speed_mul: f64,
 
-    // Points (flip-based scoring)
-    points_white: usize,
-    points_black: usize,
     points_white_el: Option<Element>,
     points_black_el: Option<Element>,
     points_dirty: bool,
 
-    raf_handle: Option<Rc<RefCell<Option<Closure<dyn FnMut(f64)>>>>>,
+    chart: ChartOverlay,
+    cell_view: CellView,
+    theme: Theme,
+    flip_anim: FlipAnim,
+    particles: Particles,
+    trails: Trails,
+    reduced_motion: bool,
+    show_fields: bool,
+    camera: Camera,
+    world: Option<(f64, f64)>, // fixed world size; `None` follows the canvas
+    fair: Option<FairMatch>,
+
+    raf_handle: Option<RafHandle>,
 }
 
-thread_local! { static APP: RefCell<Option<App>> = RefCell::new(None); }
-
-fn js_err(msg: &str) -> JsValue { JsValue::from_str(msg) }
-fn rand_range(min: f64, max: f64) -> f64 { min + (max - min) * Math::random() }
-
-impl Grid {
-    fn new(css_w: f64, css_h: f64, r: f64) -> Grid {
-        let hex_h = (3.0f64).sqrt() * r;
-        let step_x = 1.5 * r;
-
-        // columns
-        let mut cols = 0usize; let mut x = r;
-        while x + r <= css_w - 1.0 { cols += 1; x += step_x; }
-        if cols == 0 { cols = 1; }
-
-        // rows (min of even/odd columns)
-        let mut rows_even = 0usize; let mut y_even = hex_h / 2.0;
-        while y_even + hex_h / 2.0 <= css_h - 1.0 { rows_even += 1; y_even += hex_h; }
-        let mut rows_odd = 0usize; let mut y_odd = hex_h;
-        while y_odd + hex_h / 2.0 <= css_h - 1.0 { rows_odd += 1; y_odd += hex_h; }
-        let rows = rows_even.min(rows_odd).max(1);
-
-        let mut cells = Vec::with_capacity(cols * rows);
-        let mid_x = css_w * 0.5;
-        for col in 0..cols {
-            let cx = r + (col as f64) * step_x;
-            let offset_y = if col % 2 == 0 { 0.0 } else { hex_h / 2.0 };
-            for row in 0..rows {
-                let cy = hex_h / 2.0 + offset_y + (row as f64) * hex_h;
-                let color = if cx < mid_x { HexColor::White } else { HexColor::Black };
-                cells.push(Cell { col, row, cx, cy, color });
-            }
-        }
-        Grid { cells, cols, rows, r, hex_h }
-    }
-
-    #[inline]
-    fn center_to_index(&self, x: f64, y: f64) -> Option<usize> {
-        let step_x = 1.5 * self.r;
-        let hex_h = self.hex_h;
-
-        let col = ((x - self.r) / step_x).round() as isize;
-        if col < 0 || col >= self.cols as isize { return None; }
-        let col_us = col as usize;
-
-        let offset = if col_us % 2 == 0 { 0.0 } else { hex_h / 2.0 };
-        let row = ((y - hex_h / 2.0 - offset) / hex_h).round() as isize;
-        if row < 0 || row >= self.rows as isize { return None; }
-        let row_us = row as usize;
-
-        Some(col_us * self.rows + row_us)
-    }
+/// Scrolling territory/points chart drawn over the arena.
+struct ChartOverlay {
+    enabled: bool,
+    x: f64, y: f64, // CSS px from the top-left; negative values anchor to the right/bottom edge
+    w: f64, h: f64,
+    opacity: f64,
+    window: f64,    // seconds of history shown
+}
 
-    /// Set color at (x,y) to team color; return (old,new) if changed.
-    fn flip_at(&mut self, x: f64, y: f64, team: Team) -> Option<(HexColor, HexColor)> {
-        if let Some(i) = self.center_to_index(x, y) {
-            let c = &mut self.cells[i];
-            let new = match team { Team::Black => HexColor::Black, Team::White => HexColor::White };
-            if c.color != new { let old = c.color; c.color = new; return Some((old, new)); }
-        }
-        None
+impl Default for ChartOverlay {
+    fn default() -> Self {
+        ChartOverlay { enabled: false, x: 12.0, y: -12.0, w: 220.0, h: 90.0, opacity: 0.85, window: 30.0 }
     }
+}
 
-    /// Claim every hex within `radius` of `(x,y)`; returns awarded points (white, black) and bounce normal.
-    fn flip_disc(&mut self, x: f64, y: f64, radius: f64, team: Team) -> (usize, usize, Option<(f64, f64)>) {
-        let target = match team { Team::Black => HexColor::Black, Team::White => HexColor::White };
-        let mut white_pts = 0usize;
-        let mut black_pts = 0usize;
-        let r2 = radius * radius;
-        let mut nx = 0.0;
-        let mut ny = 0.0;
-        let mut hits = 0usize;
-
-        for cell in &mut self.cells {
-            let dx = cell.cx - x;
-            let dy = cell.cy - y;
-            if dx * dx + dy * dy > r2 { continue; }
-            if cell.color == target { continue; }
-
-            let old = cell.color;
-            cell.color = target;
-            match (old, target) {
-                (HexColor::Black, HexColor::White) => white_pts += 1,
-                (HexColor::White, HexColor::Black) => black_pts += 1,
-                _ => {}
-            }
-
-            let vx = x - cell.cx;
-            let vy = y - cell.cy;
-            let len = (vx * vx + vy * vy).sqrt();
-            if len > 1e-6 {
-                nx += vx / len;
-                ny += vy / len;
-                hits += 1;
-            }
-        }
+/// Two-leg side-swap match: both legs start from `seed`, with the participants trading
+/// colours in between.
+struct FairMatch {
+    seed: u64,
+    duration: f64, // sim seconds per leg
+    legs: Vec<MatchResult>, // finished legs
+}
 
-        let normal = if hits > 0 {
-            let len = (nx * nx + ny * ny).sqrt();
-            if len > 1e-6 { Some((nx / len, ny / len)) } else { None }
-        } else {
-            None
-        };
+type RafHandle = Rc<RefCell<Option<Closure<dyn FnMut(f64)>>>>;
 
-        (white_pts, black_pts, normal)
-    }
+thread_local! { static APP: RefCell<Option<App>> = const { RefCell::new(None) }; }
 
-    fn draw(&self, ctx: &CanvasRenderingContext2d) {
-        ctx.set_line_width(1.0);
-        let _ = ctx.set_line_join("round");
-        for cell in &self.cells {
-            let (fill, stroke) = match cell.color {
-                HexColor::Black => ("#000", "#fff"),
-                HexColor::White => ("#fff", "#000"),
-            };
-            ctx.set_fill_style(&JsValue::from_str(fill));
-            ctx.set_stroke_style(&JsValue::from_str(stroke));
-
-            let r = self.r;
-            ctx.begin_path();
-            for i in 0..6 {
-                let ang = (i as f64) * 60.0 * PI / 180.0;
-                let vx = cell.cx + r * ang.cos();
-                let vy = cell.cy + r * ang.sin();
-                if i == 0 { ctx.move_to(vx, vy); } else { ctx.line_to(vx, vy); }
-            }
-            ctx.close_path();
-            let _ = ctx.fill();
-            let _ = ctx.stroke();
-        }
+fn js_err(msg: &str) -> JsValue { JsValue::from_str(msg) }
+fn parse_team(name: &str) -> Result<Team, JsValue> {
+    match name.to_ascii_lowercase().as_str() {
+        "white" => Ok(Team::White),
+        "black" => Ok(Team::Black),
+        _ => Err(js_err("team must be \"white\" or \"black\"")),
     }
 }
+fn random_seed() -> u64 { (Math::random() * 9_007_199_254_740_992.0) as u64 }
 
 impl App {
     fn new(canvas: HtmlCanvasElement, ctx: CanvasRenderingContext2d, css_w: f64, css_h: f64) -> Self {
         let dpr = window().map(|w| w.device_pixel_ratio()).unwrap_or(1.0).max(1.0);
         canvas.set_width((css_w * dpr) as u32);
         canvas.set_height((css_h * dpr) as u32);
-        let _ = ctx.set_transform(dpr, 0.0, 0.0, dpr, 0.0, 0.0);
-
-        let short = css_w.min(css_h);
-        let r = (short / 50.0).clamp(3.0, 14.0);
-        let grid = Grid::new(css_w, css_h, r);
 
         let (pw_el, pb_el) = {
             if let Some(doc) = window().and_then(|w| w.document()) {
@@ -226,10 +112,20 @@ impl App {
 
         let mut app = App {
             canvas, ctx, dpr, css_w, css_h,
-            grid, balls: vec![],
+            sim: Sim::new(css_w, css_h, random_seed()),
             running: false, last_ts: 0.0, speed_mul: 1.0,
-            points_white: 0, points_black: 0,
             points_white_el: pw_el, points_black_el: pb_el, points_dirty: true,
+            chart: ChartOverlay::default(),
+            cell_view: CellView::Owner,
+            theme: Theme::classic(),
+            flip_anim: FlipAnim::default(),
+            particles: Particles::default(),
+            trails: Trails::default(),
+            reduced_motion: false,
+            show_fields: false,
+            camera: Camera::default(),
+            world: None,
+            fair: None,
             raf_handle: None,
         };
         app.update_points_dom(); // initialize scoreboard to 0/0
@@ -238,8 +134,8 @@ impl App {
 
     fn update_points_dom(&mut self) {
         if !self.points_dirty { return; }
-        if let Some(ref el) = self.points_white_el { el.set_inner_html(&self.points_white.to_string()); }
-        if let Some(ref el) = self.points_black_el { el.set_inner_html(&self.points_black.to_string()); }
+        if let Some(ref el) = self.points_white_el { el.set_inner_html(&self.sim.points_white.to_string()); }
+        if let Some(ref el) = self.points_black_el { el.set_inner_html(&self.sim.points_black.to_string()); }
         self.points_dirty = false;
     }
 
@@ -248,65 +144,39 @@ impl App {
         self.dpr = window().map(|w| w.device_pixel_ratio()).unwrap_or(1.0).max(1.0);
         self.canvas.set_width((css_w * self.dpr) as u32);
         self.canvas.set_height((css_h * self.dpr) as u32);
-        let _ = self.ctx.set_transform(self.dpr, 0.0, 0.0, self.dpr, 0.0, 0.0);
-
-        let short = css_w.min(css_h);
-        let r = (short / 50.0).clamp(3.0, 14.0);
-        self.grid = Grid::new(css_w, css_h, r);
 
-        for b in &mut self.balls {
-            b.x = b.x.clamp(b.radius, self.css_w - b.radius);
-            b.y = b.y.clamp(b.radius, self.css_h - b.radius);
+        if self.world.is_none() {
+            self.sim.resize(css_w, css_h);
+            self.camera.clamp(self.sim.width, self.sim.height);
         }
         self.render();
     }
 
+    /// Fix the world at `width`×`height` (or follow the canvas with `None`) and restart
+    /// the match on the new board.
+    fn set_world(&mut self, world: Option<(f64, f64)>) {
+        self.world = world;
+        let (w, h) = world.unwrap_or((self.css_w, self.css_h));
+        self.sim.resize(w, h);
+        self.sim.spawn_balls_per_team(self.sim.balls_per_team);
+        self.camera = Camera::default();
+        self.reset_grid();
+    }
+
+    fn letterbox(&self) -> Letterbox { Letterbox::fit(self.css_w, self.css_h, self.sim.width, self.sim.height) }
+
+    /// World point under canvas CSS pixel `(x, y)`.
+    fn screen_to_world(&self, x: f64, y: f64) -> (f64, f64) {
+        let (fx, fy) = self.letterbox().world_units(x, y);
+        self.camera.screen_to_world(fx, fy)
+    }
+
     fn set_speed(&mut self, mul: f64) { self.speed_mul = mul.clamp(0.0, 6.25); }
 
     /// PUBLIC: set balls per team (0..=5)
     fn set_balls_per_team(&mut self, per_team: u32) {
         let n = per_team.min(5);
-        self.spawn_balls_per_team(n);
-    }
-
-    fn spawn_balls_per_team(&mut self, per_team: u32) {
-        self.balls.clear();
-        let per_team = per_team as usize;
-        if per_team == 0 { return; }
-
-        let r = (self.grid.r * 1.8).clamp(6.0, 22.0);
-        let speed = (self.grid.r * 20.0).clamp(200.0, 480.0);
-
-        // White: left side, right-ish
-        for _ in 0..per_team {
-            let x = rand_range(r + 1.0, self.css_w * 0.25);
-            let y = rand_range(r + 1.0, self.css_h - r - 1.0);
-            let ang = rand_range(-0.35 * PI, 0.35 * PI);
-            self.balls.push(Ball {
-                x, y,
-                vx: ang.cos() * speed,
-                vy: ang.sin() * speed,
-                team: Team::White,
-                radius: r,
-                base_speed: speed,
-                last_bounce_ts: -1.0,
-            });
-        }
-        // Black: right side, left-ish
-        for _ in 0..per_team {
-            let x = rand_range(self.css_w * 0.75, self.css_w - r - 1.0);
-            let y = rand_range(r + 1.0, self.css_h - r - 1.0);
-            let ang = PI + rand_range(-0.35 * PI, 0.35 * PI);
-            self.balls.push(Ball {
-                x, y,
-                vx: ang.cos() * speed,
-                vy: ang.sin() * speed,
-                team: Team::Black,
-                radius: r,
-                base_speed: speed,
-                last_bounce_ts: -1.0,
-            });
-        }
+        self.sim.spawn_balls_per_team(n);
     }
 
     fn start(&mut self) -> Result<(), JsValue> {
@@ -314,7 +184,7 @@ impl App {
         self.running = true;
         self.last_ts = performance_now();
 
-        let handle: Rc<RefCell<Option<Closure<dyn FnMut(f64)>>>> = Rc::new(RefCell::new(None));
+        let handle: RafHandle = Rc::new(RefCell::new(None));
         let handle_for_loop = handle.clone();
         let win = window().ok_or_else(|| js_err("no window"))?;
         let win_loop = win.clone();
@@ -351,177 +221,108 @@ impl App {
     }
 
     fn reset_grid(&mut self) {
-        let short = self.css_w.min(self.css_h);
-        let r = (short / 50.0).clamp(3.0, 14.0);
-        self.grid = Grid::new(self.css_w, self.css_h, r);
-        self.points_white = 0;
-        self.points_black = 0;
+        self.sim.reset_grid();
+        self.particles.clear();
+        self.trails.clear();
         self.points_dirty = true;
         self.update_points_dom();
         self.render();
     }
 
+    /// Start the current leg of the fair match from its seed.
+    fn start_fair_leg(&mut self) {
+        let Some(ref fm) = self.fair else { return };
+        self.sim.rng = Rng::new(fm.seed);
+        self.sim.spawn_balls_per_team(self.sim.balls_per_team);
+        self.reset_grid();
+    }
+
+    /// End the leg once its time is up; after the second leg the match stops.
+    fn update_fair_match(&mut self) {
+        let Some(ref mut fm) = self.fair else { return };
+        if fm.legs.len() >= 2 || self.sim.clock - self.sim.match_start < fm.duration { return; }
+        fm.legs.push(MatchResult { points_white: self.sim.points_white, points_black: self.sim.points_black });
+        self.sim.swap_sides();
+        if fm.legs.len() == 1 { self.start_fair_leg(); } else { self.stop(); }
+    }
+
     fn tick(&mut self, ts: f64) -> Result<(), JsValue> {
         let dt = ((ts - self.last_ts) / 1000.0).clamp(0.0, 0.050);
         self.last_ts = ts;
-        let mul = self.speed_mul;
-        let (w, h) = (self.css_w, self.css_h);
-
-        // --- Phase 1: integrate + wall bounces ---
-        for b in &mut self.balls {
-            b.x += b.vx * dt * mul;
-            b.y += b.vy * dt * mul;
-
-            if b.x - b.radius <= 0.0 { b.x = b.radius; b.vx =  b.vx.abs(); }
-            else if b.x + b.radius >= w { b.x = w - b.radius; b.vx = -b.vx.abs(); }
-            if b.y - b.radius <= 0.0 { b.y = b.radius; b.vy =  b.vy.abs(); }
-            else if b.y + b.radius >= h { b.y = h - b.radius; b.vy = -b.vy.abs(); }
-        }
 
-        // --- Phase 2: ball-ball collisions ---
-        self.resolve_collisions();
-
-        // --- Phase 3: claim & scoring (flip-based) ---
-        let mut points_changed = false;
-        for i in 0..self.balls.len() {
-            let (x, y, radius, team, last_bounce_ts) = {
-                let b = self.balls[i];
-                (b.x, b.y, b.radius, b.team, b.last_bounce_ts)
-            };
-            let (add_white, add_black, normal) = self.grid.flip_disc(x, y, radius, team);
-            if add_white > 0 { self.points_white += add_white; points_changed = true; }
-            if add_black > 0 { self.points_black += add_black; points_changed = true; }
-            if let Some((nx, ny)) = normal {
-                let now = self.last_ts;
-                if last_bounce_ts < 0.0 || now - last_bounce_ts > 15.0 {
-                    let b = &mut self.balls[i];
-                    let dot = b.vx * nx + b.vy * ny;
-                    if dot < 0.0 {
-                        b.vx -= 2.0 * dot * nx;
-                        b.vy -= 2.0 * dot * ny;
-                        b.maintain_speed();
-                        b.last_bounce_ts = now;
-                    }
-                }
-            }
+        if self.sim.step(dt * self.speed_mul) { self.points_dirty = true; self.update_points_dom(); }
+        self.update_fair_match();
+        if !self.reduced_motion {
+            self.particles.update(dt * self.speed_mul);
+            self.particles.spawn(&self.sim.events, &self.theme);
+            self.trails.record(&self.sim.balls, self.sim.width, self.sim.height);
         }
-        if points_changed { self.points_dirty = true; self.update_points_dom(); }
 
         self.render();
         Ok(())
     }
 
-    fn resolve_collisions(&mut self) {
-        let n = self.balls.len();
-        if n < 2 { return; }
-
-        // Elastic collision, equal masses, slight restitution for liveliness
-        let restitution = 0.98;
-
-        for i in 0..n {
-            for j in (i + 1)..n {
-                // Safe split to borrow two balls mutably
-                let (left, right) = self.balls.split_at_mut(j);
-                let bi = &mut left[i];
-                let bj = &mut right[0];
-
-                let dx = bj.x - bi.x;
-                let dy = bj.y - bi.y;
-                let rsum = bi.radius + bj.radius;
-                let dist2 = dx * dx + dy * dy;
-                if dist2 > rsum * rsum { continue; }
-
-                let mut dist = dist2.sqrt();
-                if dist == 0.0 {
-                    // Rare exact overlap: poke in a random direction
-                    let ang = rand_range(0.0, PI * 2.0);
-                    dist = 1e-6;
-                    bi.x -= ang.cos() * 0.001;
-                    bi.y -= ang.sin() * 0.001;
-                }
-                // Collision normal
-                let nx = dx / dist;
-                let ny = dy / dist;
-
-                // Positional correction (separate overlap)
-                let penetration = rsum - dist;
-                let corr = (penetration / 2.0) + 1e-4;
-                bi.x -= nx * corr; bi.y -= ny * corr;
-                bj.x += nx * corr; bj.y += ny * corr;
-
-                // Relative velocity along normal
-                let rvx = bj.vx - bi.vx;
-                let rvy = bj.vy - bi.vy;
-                let vn = rvx * nx + rvy * ny;
-                if vn >= 0.0 { continue; } // moving apart
-
-                // Impulse (m1=m2=1): j = -(1+e)*vn / (1/m1+1/m2) = -(1+e)*vn/2
-                let j = -(1.0 + restitution) * vn * 0.5;
-                let jx = j * nx;
-                let jy = j * ny;
-
-                bi.vx -= jx; bi.vy -= jy;
-                bj.vx += jx; bj.vy += jy;
-
-                bi.maintain_speed();
-                bj.maintain_speed();
-
-                if bi.team == bj.team {
-                    bi.base_speed = (bi.base_speed * TEAM_BOOST).min(MAX_BASE_SPEED);
-                    bj.base_speed = (bj.base_speed * TEAM_BOOST).min(MAX_BASE_SPEED);
-                    bi.maintain_speed();
-                    bj.maintain_speed();
-                }
-            }
+    fn render(&self) {
+        let mut r = CanvasRenderer::new(&self.ctx, self.dpr);
+        let lb = self.letterbox();
+        let (zoom, x, y) = lb.view(&self.camera);
+        r.set_view(zoom, x, y);
+        let flip = if self.reduced_motion { &FlipAnim::OFF } else { &self.flip_anim };
+        render::draw_arena(&mut r, &self.sim, self.cell_view, &self.theme, flip);
+        if self.show_fields { render::draw_fields(&mut r, &self.sim); }
+        if !self.reduced_motion { self.trails.draw(&mut r, &self.sim.balls, &self.theme); }
+        render::draw_balls(&mut r, &self.sim, &self.theme);
+        if !self.reduced_motion { self.particles.draw(&mut r); }
+        r.set_view(1.0, 0.0, 0.0);
+        if self.world.is_some() {
+            // Bars go on last so anything drawn past the world edge (wrapped balls, trails) is hidden
+            let bar = self.theme.background.lerp(Rgba::rgb(0, 0, 0), 0.5);
+            let (w, h) = (self.sim.width * lb.scale, self.sim.height * lb.scale);
+            r.fill_rect(0.0, 0.0, self.css_w, lb.oy, bar);
+            r.fill_rect(0.0, lb.oy + h, self.css_w, (self.css_h - lb.oy - h).max(0.0), bar);
+            r.fill_rect(0.0, lb.oy, lb.ox, h, bar);
+            r.fill_rect(lb.ox + w, lb.oy, (self.css_w - lb.ox - w).max(0.0), h, bar);
         }
+        if self.chart.enabled { self.draw_chart(&mut r); }
     }
 
-    fn render(&self) {
-        // BG
-        self.ctx.set_fill_style(&JsValue::from_str("#111"));
-        let _ = self.ctx.fill_rect(0.0, 0.0, self.css_w, self.css_h);
-
-        // Hex grid
-        self.grid.draw(&self.ctx);
-
-        // Glossy balls
-        for b in &self.balls {
-            let r = b.radius;
-            let gx = b.x - r * 0.4;
-            let gy = b.y - r * 0.4;
-            let grad = self.ctx.create_radial_gradient(gx, gy, r * 0.05, b.x, b.y, r).unwrap();
-            match b.team {
-                Team::White => {
-                    let _ = grad.add_color_stop(0.0, "#ffffff");
-                    let _ = grad.add_color_stop(0.5, "#e9e9e9");
-                    let _ = grad.add_color_stop(1.0, "#cfcfcf");
-                    self.ctx.set_stroke_style(&JsValue::from_str("#000"));
-                }
-                Team::Black => {
-                    let _ = grad.add_color_stop(0.0, "#6b6b6b");
-                    let _ = grad.add_color_stop(0.5, "#181818");
-                    let _ = grad.add_color_stop(1.0, "#000000");
-                    self.ctx.set_stroke_style(&JsValue::from_str("#fff"));
-                }
-            }
-            self.ctx.set_fill_style(&grad);
-
-            self.ctx.begin_path();
-            let _ = self.ctx.arc(b.x, b.y, r, 0.0, PI * 2.0);
-            let _ = self.ctx.fill();
-
-            // specular dot
-            self.ctx.set_global_alpha(0.55);
-            self.ctx.set_fill_style(&JsValue::from_str("#ffffff"));
-            self.ctx.begin_path();
-            let dot_r = (r * 0.28).max(0.8);
-            let _ = self.ctx.arc(b.x - r * 0.45, b.y - r * 0.45, dot_r, 0.0, PI * 2.0);
-            let _ = self.ctx.fill();
-            self.ctx.set_global_alpha(1.0);
-
-            self.ctx.set_line_width(1.0);
-            let _ = self.ctx.stroke();
+    /// Territory share (solid) and points (dashed, scaled to the window's max) per team.
+    fn draw_chart(&self, r: &mut dyn Renderer) {
+        let c = &self.chart;
+        let (w, h) = (c.w.min(self.css_w), c.h.min(self.css_h));
+        let x0 = if c.x < 0.0 { self.css_w + c.x - w } else { c.x };
+        let y0 = if c.y < 0.0 { self.css_h + c.y - h } else { c.y };
+        let sim = &self.sim;
+        let now = sim.clock;
+        let since = (now - c.window).max(sim.match_start);
+        let mut pts: Vec<Sample> = sim.series.samples.iter().copied().filter(|s| s.t >= since).collect();
+        let (tw, tb) = sim.grid.territory();
+        let total = (tw + tb).max(1) as f64;
+        pts.push(Sample {
+            t: now,
+            white_share: tw as f64 / total, black_share: tb as f64 / total,
+            points_white: sim.points_white, points_black: sim.points_black,
+        });
+        let max_pts = pts.iter().map(|s| s.points_white.max(s.points_black)).max().unwrap_or(0).max(1) as f64;
+
+        r.set_alpha(c.opacity);
+        r.fill_rect(x0, y0, w, h, Rgba::hex("#7a7a7a"));
+        r.stroke_polyline(&[(x0, y0 + h * 0.5), (x0 + w, y0 + h * 0.5)], Rgba::hex("#5c5c5c"), 1.0, None);
+
+        let px = |t: f64| x0 + w * ((t - (now - c.window)) / c.window).clamp(0.0, 1.0);
+        let py = |v: f64| y0 + h * (1.0 - v);
+        for (color, white) in [(self.theme.team(Team::White).accent, true), (self.theme.team(Team::Black).accent, false)] {
+            let share = |s: &Sample| if white { s.white_share } else { s.black_share };
+            let points = |s: &Sample| if white { s.points_white } else { s.points_black };
+            let line: Vec<(f64, f64)> = pts.iter().map(|s| (px(s.t), py(share(s)))).collect();
+            r.stroke_polyline(&line, color, 2.0, None);
+            let line: Vec<(f64, f64)> = pts.iter().map(|s| (px(s.t), py(points(s) as f64 / max_pts))).collect();
+            r.stroke_polyline(&line, color, 1.0, Some((4.0, 3.0)));
         }
+
+        r.fill_text(&format!("{:.0}%", tw as f64 / total * 100.0), x0 + 4.0, y0 + 3.0, 11.0, self.theme.team(Team::White).accent, TextAnchor::TopLeft);
+        r.fill_text(&format!("{:.0}%", tb as f64 / total * 100.0), x0 + w - 4.0, y0 + 3.0, 11.0, self.theme.team(Team::Black).accent, TextAnchor::TopRight);
+        r.set_alpha(1.0);
     }
 }
 
@@ -557,4 +358,538 @@ pub fn init_app(canvas_id: &str, css_w: f64, css_h: f64, balls_per_team: u32, sp
 
 #[wasm_bindgen] pub fn set_num_balls(n: u32) { set_balls_per_team(n); }
 
+/// Drop a `team` ball at world point `(x, y)` with velocity `(vx, vy)` px/s and `radius`
+/// (up to 60) into the running match, leaving the other balls and the grid alone. A zero
+/// velocity picks a random heading at the standard speed; a zero radius the standard
+/// size. Radii too big for the world are shrunk to fit, and speeds under 60 px/s raised to
+/// that. Returns the ball's id, which stays valid until the ball is removed or the team is
+/// respawned.
+#[wasm_bindgen] pub fn add_ball(team: &str, x: f64, y: f64, vx: f64, vy: f64, radius: f64) -> Result<u32, JsValue> {
+    let team = parse_team(team)?;
+    if ![x, y, vx, vy, radius].iter().all(|v| v.is_finite()) { return Err(js_err("ball parameters must be finite")); }
+    if !(0.0..=60.0).contains(&radius) { return Err(js_err("radius must be 0..=60")); }
+    APP.with(|a| match *a.borrow_mut() {
+        Some(ref mut app) => {
+            let id = app.sim.add_ball(team, (x, y), (vx, vy), radius);
+            app.render();
+            Ok(id)
+        }
+        None => Err(js_err("app not initialized")),
+    })
+}
+
+/// Remove the ball with `id`; returns false if there is none.
+#[wasm_bindgen] pub fn remove_ball(id: u32) -> bool {
+    APP.with(|a| match *a.borrow_mut() {
+        Some(ref mut app) => {
+            let removed = app.sim.remove_ball(id);
+            if removed { app.render(); }
+            removed
+        }
+        None => false,
+    })
+}
+
+/// Id of the topmost ball covering world point `(x, y)`, if any.
+#[wasm_bindgen] pub fn ball_at(x: f64, y: f64) -> Option<u32> {
+    APP.with(|a| a.borrow().as_ref().and_then(|app| app.sim.ball_at(x, y)))
+}
+
+/// Per-team spawn mix: of each team's balls, `heavy` spawn big/slow and `light` small/fast.
+#[wasm_bindgen] pub fn set_ball_mix(team: &str, heavy: u32, light: u32) -> Result<(), JsValue> {
+    let team = parse_team(team)?;
+    APP.with(|a| if let Some(ref mut app) = *a.borrow_mut() { app.sim.set_ball_mix(team, heavy, light); });
+    Ok(())
+}
+
+/// Where balls start: "sides" (random in each team's quarter), "mirrored" (black is the
+/// point reflection of white), "line" or "wedge" formations, "zones" (inside the areas
+/// from `add_spawn_zone`) or "random" (anywhere). Respawns balls.
+#[wasm_bindgen] pub fn set_spawn_layout(name: &str) -> Result<(), JsValue> {
+    let layout = SpawnLayout::parse(name)
+        .ok_or_else(|| js_err(&format!("unknown layout \"{}\"; expected one of {}", name, SpawnLayout::NAMES.join(", "))))?;
+    APP.with(|a| if let Some(ref mut app) = *a.borrow_mut() {
+        app.sim.spawn_layout = layout;
+        app.sim.spawn_balls_per_team(app.sim.balls_per_team);
+        app.trails.clear();
+        app.render();
+    });
+    Ok(())
+}
+
+/// Add a spawn area for `team` in world units, used by the "zones" layout. A team without
+/// zones spawns as in "sides". Takes effect at the next respawn.
+#[wasm_bindgen] pub fn add_spawn_zone(team: &str, x: f64, y: f64, width: f64, height: f64) -> Result<(), JsValue> {
+    let team = parse_team(team)?;
+    if ![x, y, width, height].iter().all(|v| v.is_finite()) || width < 0.0 || height < 0.0 {
+        return Err(js_err("zone must be finite with a non-negative size"));
+    }
+    APP.with(|a| if let Some(ref mut app) = *a.borrow_mut() { app.sim.spawn_zones[team.index()].push((x, y, width, height)); });
+    Ok(())
+}
+
+#[wasm_bindgen] pub fn clear_spawn_zones() {
+    APP.with(|a| if let Some(ref mut app) = *a.borrow_mut() { app.sim.spawn_zones = [vec![], vec![]]; })
+}
+
+/// Per-team handicap for balance experiments: `extra_balls` (-5..=5) added to the balls
+/// per team, multipliers (0.25..=4) on spawn speed, ball radius and claim radius, and a
+/// cruising speed cap `max_speed` in px/s (50..=2000, 0 for the default). Balls are never
+/// spawned too big to fit the world, whatever the radius multiplier. Respawns balls;
+/// `set_handicap(team, 0, 1, 1, 1, 0)` clears it.
+#[wasm_bindgen] pub fn set_handicap(team: &str, extra_balls: i32, speed: f64, radius: f64, claim: f64, max_speed: f64) -> Result<(), JsValue> {
+    let team = parse_team(team)?;
+    if !(-5..=5).contains(&extra_balls) { return Err(js_err("extra_balls must be -5..=5")); }
+    if ![speed, radius, claim].iter().all(|m| (0.25..=4.0).contains(m)) {
+        return Err(js_err("speed, radius and claim multipliers must be 0.25..=4"));
+    }
+    let max_speed = match max_speed {
+        0.0 => MAX_BASE_SPEED,
+        v if (50.0..=2000.0).contains(&v) => v,
+        _ => return Err(js_err("max_speed must be 50..=2000, or 0 for the default")),
+    };
+    APP.with(|a| if let Some(ref mut app) = *a.borrow_mut() {
+        app.sim.handicaps[team.index()] = Handicap { extra_balls, speed, radius, claim, max_speed };
+        app.sim.spawn_balls_per_team(app.sim.balls_per_team);
+        app.trails.clear();
+        app.render();
+    });
+    Ok(())
+}
+
+/// Power-up spawn rules: on/off, seconds between spawns, max pickups on board, effect duration.
+#[wasm_bindgen] pub fn set_powerups(enabled: bool, interval_secs: f64, max_active: u32, duration_secs: f64) {
+    APP.with(|a| if let Some(ref mut app) = *a.borrow_mut() {
+        let cfg = &mut app.sim.powerup_cfg;
+        cfg.enabled = enabled;
+        cfg.interval = interval_secs.max(0.1);
+        cfg.max_active = max_active as usize;
+        cfg.duration = duration_secs.max(0.0);
+        if !enabled { app.sim.powerups.clear(); app.sim.powerup_timer = 0.0; }
+    })
+}
+
+/// Enable or disable one kind: "speed", "grow", "paint_bomb", "shield" or "split".
+#[wasm_bindgen] pub fn set_powerup_kind(kind: &str, enabled: bool) -> Result<(), JsValue> {
+    let kind = PowerUpKind::parse(kind).ok_or_else(|| js_err("unknown power-up kind"))?;
+    APP.with(|a| if let Some(ref mut app) = *a.borrow_mut() { app.sim.powerup_cfg.kinds[kind.index()] = enabled; });
+    Ok(())
+}
+
+/// Pin power-up spawns to a hex; once any are set, pickups only appear on these.
+#[wasm_bindgen] pub fn add_powerup_spawn(col: u32, row: u32) {
+    APP.with(|a| if let Some(ref mut app) = *a.borrow_mut() { app.sim.powerup_cfg.spawn_hexes.push((col as usize, row as usize)); })
+}
+
+#[wasm_bindgen] pub fn clear_powerup_spawns() {
+    APP.with(|a| if let Some(ref mut app) = *a.borrow_mut() { app.sim.powerup_cfg.spawn_hexes.clear(); })
+}
+
+/// Mark `team`'s first ball as player-controlled (or release it back to the simulation).
+#[wasm_bindgen] pub fn set_player_control(team: &str, enabled: bool) -> Result<(), JsValue> {
+    let team = parse_team(team)?;
+    APP.with(|a| if let Some(ref mut app) = *a.borrow_mut() { app.sim.set_player_control(team, enabled); });
+    Ok(())
+}
+
+/// Keyboard steering for `team`'s player ball; `(x,y)` is a direction, clamped to unit length.
+#[wasm_bindgen] pub fn player_steer(team: &str, x: f64, y: f64) -> Result<(), JsValue> {
+    let team = parse_team(team)?;
+    if !x.is_finite() || !y.is_finite() { return Err(js_err("direction must be finite")); }
+    APP.with(|a| if let Some(ref mut app) = *a.borrow_mut() { app.sim.players[team.index()].dir = (x, y); });
+    Ok(())
+}
+
+/// Pointer steering: `team`'s player ball turns toward the world point under canvas CSS
+/// pixel `(x,y)`.
+#[wasm_bindgen] pub fn player_target(team: &str, x: f64, y: f64) -> Result<(), JsValue> {
+    let team = parse_team(team)?;
+    if !x.is_finite() || !y.is_finite() { return Err(js_err("target must be finite")); }
+    APP.with(|a| if let Some(ref mut app) = *a.borrow_mut() {
+        app.sim.players[team.index()].target = Some(app.screen_to_world(x, y));
+    });
+    Ok(())
+}
+
+#[wasm_bindgen] pub fn clear_player_target(team: &str) -> Result<(), JsValue> {
+    let team = parse_team(team)?;
+    APP.with(|a| if let Some(ref mut app) = *a.borrow_mut() { app.sim.players[team.index()].target = None; });
+    Ok(())
+}
+
+/// Assign a built-in AI to `team`: "regions", "defend", "chase", "wander", or "none".
+#[wasm_bindgen] pub fn set_team_controller(team: &str, name: &str) -> Result<(), JsValue> {
+    let team = parse_team(team)?;
+    let ctrl = if name.eq_ignore_ascii_case("none") {
+        None
+    } else {
+        Some(ai::controller_by_name(name).ok_or_else(|| js_err("unknown controller"))?)
+    };
+    APP.with(|a| if let Some(ref mut app) = *a.borrow_mut() { app.sim.controllers[team.index()] = ctrl; });
+    Ok(())
+}
+
+/// Round-robin tournament over headless matches. `entrants` is a comma-separated list of
+/// `controller` or `controller:heavy/light` specs (e.g. `"regions,chase:2/0,none"`); every
+/// pair plays each seed twice with sides swapped. `seeds` is capped at 20, `match_secs` at
+/// 300, entrants at 8 and the whole run at 20000 simulated seconds. Returns Elo ratings and
+/// a win matrix as `format` "csv" or "json".
+#[wasm_bindgen]
+pub fn run_tournament(entrants: &str, seeds: u32, match_secs: f64, balls_per_team: u32, format: &str) -> Result<String, JsValue> {
+    let entrants = entrants.split(',')
+        .filter(|s| !s.trim().is_empty())
+        .map(tournament::Entrant::parse)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| js_err(&e))?;
+    if entrants.len() < 2 { return Err(js_err("need at least two entrants")); }
+    if entrants.len() > tournament::MAX_ENTRANTS {
+        return Err(js_err(&format!("at most {} entrants", tournament::MAX_ENTRANTS)));
+    }
+    if !(match_secs > 0.0 && match_secs <= tournament::MAX_MATCH_SECS) {
+        return Err(js_err(&format!("match_secs must be above 0 and at most {}", tournament::MAX_MATCH_SECS)));
+    }
+    let seeds = seeds.clamp(1, tournament::MAX_SEEDS);
+    let pairs = entrants.len() * (entrants.len() - 1) / 2;
+    let total = (pairs * 2) as f64 * seeds as f64 * match_secs;
+    if total > tournament::MAX_TOTAL_SECS {
+        return Err(js_err(&format!("{} simulated seconds is over the {} limit; use fewer entrants, seeds or match_secs",
+            total, tournament::MAX_TOTAL_SECS)));
+    }
+
+    let settings = tournament::MatchSettings {
+        balls_per_team: balls_per_team.min(5),
+        duration: match_secs,
+        ..tournament::MatchSettings::default()
+    };
+    let standings = tournament::run(&entrants, &settings, seeds, random_seed());
+    match format {
+        "csv" => Ok(standings.to_csv()),
+        "json" => Ok(standings.to_json()),
+        _ => Err(js_err("format must be \"csv\" or \"json\"")),
+    }
+}
+
+/// Headless fair match: `seed` (0 picks one) played twice between two entrant specs as in
+/// `run_tournament`, `first` taking white in leg one and black in leg two, so both get each
+/// colour, side and spawn. Legs last at most 300 sim seconds. `layout` as in `set_spawn_layout`.
+/// Returns the `get_fair_match` JSON.
+#[wasm_bindgen]
+pub fn run_fair_match(first: &str, second: &str, seed: u32, match_secs: f64, balls_per_team: u32, layout: &str) -> Result<String, JsValue> {
+    let first = tournament::Entrant::parse(first).map_err(|e| js_err(&e))?;
+    let second = tournament::Entrant::parse(second).map_err(|e| js_err(&e))?;
+    if !(match_secs > 0.0 && match_secs <= tournament::MAX_MATCH_SECS) {
+        return Err(js_err(&format!("match_secs must be above 0 and at most {}", tournament::MAX_MATCH_SECS)));
+    }
+    let layout = SpawnLayout::parse(layout).ok_or_else(|| js_err("unknown layout"))?;
+    let settings = tournament::MatchSettings {
+        balls_per_team: balls_per_team.min(5),
+        duration: match_secs,
+        layout,
+        ..tournament::MatchSettings::default()
+    };
+    let seed = if seed == 0 { random_seed() } else { seed as u64 };
+    let legs = tournament::play_fair_pair(&settings, &first, &second, seed);
+    Ok(tournament::fair_json(seed, &legs))
+}
+
+/// Play the current setup as a two-leg fair match of `leg_secs` sim seconds per leg. Both
+/// legs start from `seed` (0 picks one); for the second, the teams' controllers, ball mixes
+/// and player control swap colours, so each side plays both spawns. Starts the loop; it
+/// stops after the second leg. Poll `get_fair_match` for progress and the verdict.
+#[wasm_bindgen] pub fn start_fair_match(leg_secs: f64, seed: u32) -> Result<(), JsValue> {
+    if leg_secs.is_nan() || leg_secs <= 0.0 { return Err(js_err("leg_secs must be positive")); }
+    let seed = if seed == 0 { random_seed() } else { seed as u64 };
+    APP.with(|a| match *a.borrow_mut() {
+        Some(ref mut app) => {
+            // A fair match abandoned half-way leaves the sides swapped; put them back first
+            if app.fair.as_ref().is_some_and(|fm| fm.legs.len() == 1) { app.sim.swap_sides(); }
+            app.fair = Some(FairMatch { seed, duration: leg_secs, legs: vec![] });
+            app.start_fair_leg();
+            app.start()
+        }
+        None => Err(js_err("app not initialized")),
+    })
+}
+
+/// Fair match progress: `{ seed, leg, leg_secs, elapsed, swapped, legs: [{ white, black }],
+/// first, second, verdict }`. `leg` is 1 or 2 while playing and 0 once done; `swapped` is
+/// true while the sides hold each other's colours, so player input for the side that
+/// started as white goes to "black" and vice versa; `first` and `second` are combined
+/// points of the side that started as white and black; `verdict` is "first", "second" or
+/// "draw" when done, otherwise null. Null if no fair match was started.
+#[wasm_bindgen] pub fn get_fair_match() -> Result<JsValue, JsValue> {
+    let json = APP.with(|a| a.borrow().as_ref().map(|app| match app.fair {
+        Some(ref fm) => {
+            let leg = if fm.legs.len() >= 2 { 0 } else { fm.legs.len() + 1 };
+            let elapsed = if leg == 0 { fm.duration } else { (app.sim.clock - app.sim.match_start).min(fm.duration) };
+            format!("{{\"leg\":{},\"leg_secs\":{},\"elapsed\":{},\"swapped\":{},{}}}",
+                leg, json::number(fm.duration), json::number(elapsed), leg == 2, tournament::fair_fields(fm.seed, &fm.legs))
+        }
+        None => "null".to_string(),
+    }));
+    let json = json.ok_or_else(|| js_err("app not initialized"))?;
+    js_sys::JSON::parse(&json)
+}
+
+/// Match statistics since the last reset:
+/// `{ time, teams: { white, black }, balls: [...], series: { interval, t, white, black, points_white, points_black } }`.
+/// Per ball: id, cells flipped/stolen, distance, collisions, wall and territory bounces, peak speed.
+#[wasm_bindgen] pub fn get_stats() -> Result<JsValue, JsValue> {
+    let json = APP.with(|a| a.borrow().as_ref().map(|app| stats::to_json(&app.sim)));
+    let json = json.ok_or_else(|| js_err("app not initialized"))?;
+    js_sys::JSON::parse(&json)
+}
+
+/// Territory chart overlay. `x`/`y` are CSS px from the top-left corner; negative values
+/// anchor to the right/bottom edge. `opacity` is 0..=1.
+#[wasm_bindgen] pub fn set_chart_overlay(enabled: bool, x: f64, y: f64, width: f64, height: f64, opacity: f64) {
+    APP.with(|a| if let Some(ref mut app) = *a.borrow_mut() {
+        app.chart = ChartOverlay { enabled, x, y, w: width.max(40.0), h: height.max(24.0), opacity, ..ChartOverlay::default() };
+        app.render();
+    })
+}
+
+/// Fill hexes by "owner" (team colour) or "heat" (how often each hex flipped).
+#[wasm_bindgen] pub fn set_cell_view(mode: &str) -> Result<(), JsValue> {
+    let view = match mode {
+        "owner" => CellView::Owner,
+        "heat" => CellView::Heat,
+        _ => return Err(js_err("mode must be \"owner\" or \"heat\"")),
+    };
+    APP.with(|a| if let Some(ref mut app) = *a.borrow_mut() { app.cell_view = view; app.render(); });
+    Ok(())
+}
+
+/// Switch colour theme by name (see `THEME_NAMES`); applies from the next frame, or at once
+/// while stopped. The match carries on untouched.
+#[wasm_bindgen] pub fn set_theme(name: &str) -> Result<(), JsValue> {
+    let theme = Theme::by_name(name)
+        .ok_or_else(|| js_err(&format!("unknown theme \"{}\"; expected one of {}", name, THEME_NAMES.join(", "))))?;
+    APP.with(|a| if let Some(ref mut app) = *a.borrow_mut() { app.theme = theme; app.render(); });
+    Ok(())
+}
+
+/// How hexes animate when they change owner: "none", "fade", "pop" or "rotate", over
+/// `duration_secs` of sim time (0..=2). Visual only; scoring is unaffected.
+#[wasm_bindgen] pub fn set_flip_animation(style: &str, duration_secs: f64) -> Result<(), JsValue> {
+    let style = match style {
+        "none" => FlipStyle::None,
+        "fade" => FlipStyle::Fade,
+        "pop" => FlipStyle::Pop,
+        "rotate" => FlipStyle::Rotate,
+        _ => return Err(js_err("style must be \"none\", \"fade\", \"pop\" or \"rotate\"")),
+    };
+    let duration = if duration_secs.is_finite() { duration_secs.clamp(0.0, 2.0) } else { 0.0 };
+    APP.with(|a| if let Some(ref mut app) = *a.borrow_mut() { app.flip_anim = FlipAnim { style, duration }; });
+    Ok(())
+}
+
+/// Particle effects (impact sparks, capture chips, wall dust): 0 turns them off, 1 is the
+/// default amount, up to 3. Visual only.
+#[wasm_bindgen] pub fn set_particles(intensity: f64) {
+    let intensity = if intensity.is_finite() { intensity.clamp(0.0, 3.0) } else { 1.0 };
+    APP.with(|a| if let Some(ref mut app) = *a.borrow_mut() {
+        app.particles.intensity = intensity;
+        if intensity == 0.0 { app.particles.clear(); }
+    });
+}
+
+/// Override the current theme's ball trails: `kind` is "off", "line" or "ghosts", `length`
+/// the number of frames kept (up to 48), `opacity` at the ball end. `set_theme` restores
+/// the theme's own trails.
+#[wasm_bindgen] pub fn set_trails(kind: &str, length: u32, opacity: f64) -> Result<(), JsValue> {
+    let kind = match kind {
+        "off" => TrailKind::Off,
+        "line" => TrailKind::Line,
+        "ghosts" => TrailKind::Ghosts,
+        _ => return Err(js_err("kind must be \"off\", \"line\" or \"ghosts\"")),
+    };
+    let length = (length as usize).min(trails::MAX_LEN);
+    let opacity = if opacity.is_finite() { opacity.clamp(0.0, 1.0) } else { 0.0 };
+    APP.with(|a| if let Some(ref mut app) = *a.borrow_mut() { app.theme.trail = TrailStyle { kind, length, opacity }; });
+    Ok(())
+}
+
+/// Reduced-motion mode: no trails, particles or flip animations (hexes change instantly).
+/// The page sets this from `prefers-reduced-motion`.
+#[wasm_bindgen] pub fn set_reduced_motion(on: bool) {
+    APP.with(|a| if let Some(ref mut app) = *a.borrow_mut() {
+        app.reduced_motion = on;
+        if on { app.particles.clear(); app.trails.clear(); }
+        app.render();
+    });
+}
+
+/// Per-hex activity since the last reset:
+/// `{ cols, rows, flips, white_secs, black_secs, longest_owner }`, each matrix indexed `[row][col]`.
+#[wasm_bindgen] pub fn get_heatmap() -> Result<JsValue, JsValue> {
+    let json = APP.with(|a| a.borrow().as_ref().map(|app| stats::heatmap_json(&app.sim)));
+    let json = json.ok_or_else(|| js_err("app not initialized"))?;
+    js_sys::JSON::parse(&json)
+}
+
+/// The current board as a standalone SVG document: hexes, glossy balls, and (optionally)
+/// a score caption. Uses the same geometry as the canvas renderer.
+#[wasm_bindgen] pub fn export_svg(caption: bool) -> Result<String, JsValue> {
+    APP.with(|a| a.borrow().as_ref().map(|app| svg::export(&app.sim, &app.theme, caption)))
+        .ok_or_else(|| js_err("app not initialized"))
+}
+
+/// The current board rendered in software and encoded as PNG, `scale` device pixels per
+/// CSS pixel (clamped to 0.25..=4). Overlays and power-up glyphs are not included.
+#[wasm_bindgen] pub fn export_png(scale: f64) -> Result<Vec<u8>, JsValue> {
+    let scale = if scale.is_finite() { scale.clamp(0.25, 4.0) } else { 1.0 };
+    APP.with(|a| a.borrow().as_ref().map(|app| raster::snapshot(&app.sim, app.cell_view, &app.theme, scale).to_png()))
+        .ok_or_else(|| js_err("app not initialized"))
+}
+
+/// Zoom by `factor` (1 = no change) about canvas CSS pixel `(x, y)`; zoom stays in 1..=8.
+#[wasm_bindgen] pub fn camera_zoom_at(x: f64, y: f64, factor: f64) {
+    APP.with(|a| if let Some(ref mut app) = *a.borrow_mut() {
+        let (fx, fy) = app.letterbox().world_units(x, y);
+        app.camera.zoom_at(fx, fy, factor, app.sim.width, app.sim.height);
+        app.render();
+    })
+}
+
+/// Pan by a drag of `(dx, dy)` canvas CSS pixels.
+#[wasm_bindgen] pub fn camera_pan(dx: f64, dy: f64) {
+    APP.with(|a| if let Some(ref mut app) = *a.borrow_mut() {
+        let k = app.letterbox().scale;
+        app.camera.pan(dx / k, dy / k, app.sim.width, app.sim.height);
+        app.render();
+    })
+}
+
+/// Back to the whole arena.
+#[wasm_bindgen] pub fn camera_reset() {
+    APP.with(|a| if let Some(ref mut app) = *a.borrow_mut() { app.camera = Camera::default(); app.render(); })
+}
+
+/// World coordinates `[x, y]` under canvas CSS pixel `(x, y)`, through the letterbox and camera.
+#[wasm_bindgen] pub fn screen_to_world(x: f64, y: f64) -> Vec<f64> {
+    APP.with(|a| a.borrow().as_ref().map(|app| app.screen_to_world(x, y)))
+        .map(|(wx, wy)| vec![wx, wy])
+        .unwrap_or_else(|| vec![x, y])
+}
+
+/// Toroidal arena: balls leaving one edge reappear on the opposite one, and claiming,
+/// collisions and pickups reach across the seam. Turning it off pulls balls back inside.
+/// The hexes are laid out to tile across the seams, so either way the board restarts.
+#[wasm_bindgen] pub fn set_wrap(on: bool) {
+    APP.with(|a| if let Some(ref mut app) = *a.borrow_mut() {
+        app.sim.set_wrap(on);
+        app.reset_grid();
+    })
+}
+
+/// What `edge` ("left", "right", "top", "bottom" or "all") does to balls that hit it:
+/// - "elastic": perfect mirror
+/// - "damped": mirror keeping `amount` (0..=1) of the ball's cruising speed
+/// - "scatter": mirror turned by a random angle of up to `amount` degrees (0..=80)
+/// - "sticky": holds the ball for `amount` seconds (0..=5), then releases it mirrored
+/// - "white" / "black": owned by that team, whose balls pass through and come back on the
+///   opposite edge while the enemy bounces
+///
+/// Walls are ignored while wrap-around is on.
+#[wasm_bindgen] pub fn set_wall(edge: &str, kind: &str, amount: f64) -> Result<(), JsValue> {
+    let edges: Vec<usize> = match edge {
+        "all" => (0..EDGE_NAMES.len()).collect(),
+        _ => vec![EDGE_NAMES.iter().position(|&e| e == edge)
+            .ok_or_else(|| js_err("edge must be \"left\", \"right\", \"top\", \"bottom\" or \"all\""))?],
+    };
+    let in_range = |lo: f64, hi: f64, what: &str| {
+        if (lo..=hi).contains(&amount) { Ok(amount) } else { Err(js_err(&format!("{} wall amount must be {}..={}", what, lo, hi))) }
+    };
+    let kind = match kind {
+        "elastic" => WallKind::Elastic,
+        "damped" => WallKind::Damped(in_range(0.0, 1.0, kind)?),
+        "scatter" => WallKind::Scatter(in_range(0.0, 80.0, kind)?.to_radians()),
+        "sticky" => WallKind::Sticky(in_range(0.0, 5.0, kind)?),
+        "white" | "black" => WallKind::Owned(parse_team(kind)?),
+        _ => return Err(js_err("kind must be \"elastic\", \"damped\", \"scatter\", \"sticky\", \"white\" or \"black\"")),
+    };
+    APP.with(|a| if let Some(ref mut app) = *a.borrow_mut() {
+        for i in edges { app.sim.walls[i] = kind; }
+    });
+    Ok(())
+}
+
+/// Place a force field centred on world point `(x, y)`. `kind` is
+/// "attract", "repel", "wind" (pushing along `angle_deg`, 0 = right, 90 = down) or
+/// "vortex" (clockwise; negative `strength` turns it anticlockwise). `strength` is the
+/// peak acceleration in px/s² (up to 5000), fading to nothing at `radius` (up to 20000)
+/// except for wind; a `radius` of 0 covers the whole arena. Fields turn balls without changing their speed.
+#[wasm_bindgen] pub fn add_force_field(kind: &str, x: f64, y: f64, radius: f64, strength: f64, angle_deg: f64) -> Result<(), JsValue> {
+    if ![x, y, radius, strength, angle_deg].iter().all(|v| v.is_finite()) { return Err(js_err("field parameters must be finite")); }
+    if !(0.0..=20_000.0).contains(&radius) { return Err(js_err("radius must be 0..=20000")); }
+    if strength.abs() > 5000.0 { return Err(js_err("strength must be within ±5000")); }
+    let (kind, strength) = match kind {
+        "attract" => (FieldKind::Point, strength),
+        "repel" => (FieldKind::Point, -strength),
+        "wind" => (FieldKind::Wind(angle_deg.to_radians()), strength),
+        "vortex" => (FieldKind::Vortex, strength),
+        _ => return Err(js_err("kind must be \"attract\", \"repel\", \"wind\" or \"vortex\"")),
+    };
+    APP.with(|a| if let Some(ref mut app) = *a.borrow_mut() {
+        app.sim.fields.push(ForceField { kind, x, y, radius, strength });
+        app.render();
+    });
+    Ok(())
+}
+
+#[wasm_bindgen] pub fn clear_force_fields() {
+    APP.with(|a| if let Some(ref mut app) = *a.borrow_mut() { app.sim.fields.clear(); app.render(); })
+}
+
+/// Draw force fields as faint arrows. Visual only.
+#[wasm_bindgen] pub fn set_show_fields(on: bool) {
+    APP.with(|a| if let Some(ref mut app) = *a.borrow_mut() { app.show_fields = on; app.render(); })
+}
+
+/// Grid by hex radius in world units; 0 goes back to the automatic size for the world.
+/// Rebuilds the grid, respawns balls sized to the new hexes and restarts the match. A later
+/// resize that would take the grid past 250k cells goes back to the automatic size.
+#[wasm_bindgen] pub fn set_hex_radius(radius: f64) -> Result<(), JsValue> {
+    let res = if radius == 0.0 { GridResolution::Auto } else { GridResolution::Radius(radius) };
+    set_resolution(res)
+}
+
+/// Grid by count: the largest hexes giving at least `cols` columns and `rows` rows (0 leaves
+/// that axis free), e.g. `300, 0` for a dense board or `15, 0` for a chunky one. Rebuilds
+/// like `set_hex_radius`.
+#[wasm_bindgen] pub fn set_grid_size(cols: u32, rows: u32) -> Result<(), JsValue> {
+    set_resolution(GridResolution::Cells { cols: cols as usize, rows: rows as usize })
+}
+
+fn set_resolution(res: GridResolution) -> Result<(), JsValue> {
+    APP.with(|a| match *a.borrow_mut() {
+        Some(ref mut app) => {
+            app.sim.set_resolution(res).map_err(|e| js_err(&e))?;
+            app.reset_grid();
+            Ok(())
+        }
+        None => Err(js_err("app not initialized")),
+    })
+}
+
+/// Fix the world at `width`×`height` world units, scaled and letterboxed into the canvas,
+/// so the board is the same on every screen; `0, 0` follows the canvas size again. Both
+/// sides must be 100..=10000. Restarts the match.
+#[wasm_bindgen] pub fn set_world_size(width: f64, height: f64) -> Result<(), JsValue> {
+    let world = if width == 0.0 && height == 0.0 {
+        None
+    } else if (100.0..=10_000.0).contains(&width) && (100.0..=10_000.0).contains(&height) {
+        Some((width, height))
+    } else {
+        return Err(js_err("world size must be 100..=10000 per side, or 0, 0 to follow the canvas"));
+    };
+    APP.with(|a| if let Some(ref mut app) = *a.borrow_mut() { app.set_world(world); });
+    Ok(())
+}
+
+/// Current world size in world units (the canvas size unless fixed with `set_world_size`).
+#[wasm_bindgen] pub fn world_width() -> f64 { APP.with(|a| a.borrow().as_ref().map(|app| app.sim.width).unwrap_or(0.0)) }
+#[wasm_bindgen] pub fn world_height() -> f64 { APP.with(|a| a.borrow().as_ref().map(|app| app.sim.height).unwrap_or(0.0)) }
+
 #[wasm_bindgen] pub fn resize(css_w: f64, css_h: f64) { APP.with(|a| if let Some(ref mut app) = *a.borrow_mut() { app.resize(css_w, css_h); }) }
@@ -0,0 +1,144 @@
+use std::f64::consts::PI;
+
+use crate::render::{Paint, Renderer, Rgba};
+use crate::sim::{Rng, SimEvent};
+use crate::theme::Theme;
+
+const POOL_SIZE: usize = 1024;
+const DRAG: f64 = 3.0; // fraction of velocity lost per second
+
+#[derive(Clone, Copy)]
+enum Shape { Spark, Chip, Dust }
+
+#[derive(Clone, Copy)]
+struct Particle {
+    x: f64, y: f64,
+    vx: f64, vy: f64,
+    age: f64, life: f64, // seconds
+    size: f64,
+    color: Rgba,
+    shape: Shape,
+}
+
+/// Short-lived debris spawned from `SimEvent`s: sparks at impacts, chips of the old colour
+/// at captured hexes, dust at wall bounces. The pool is allocated once; when it is full
+/// new particles overwrite old ones. It has its own RNG so seeded matches are unaffected.
+pub(crate) struct Particles {
+    pool: Vec<Particle>,
+    cursor: usize, // next slot to overwrite once full
+    pub intensity: f64, // particle count multiplier; 0 turns effects off
+    rng: Rng,
+}
+
+impl Default for Particles {
+    fn default() -> Self {
+        Particles { pool: Vec::with_capacity(POOL_SIZE), cursor: 0, intensity: 1.0, rng: Rng::new(0x5eed) }
+    }
+}
+
+impl Particles {
+    pub fn clear(&mut self) { self.pool.clear(); }
+
+    /// Add one particle moving at `speed` along `angle`; life and size are rolled from their ranges.
+    fn emit(&mut self, (x, y): (f64, f64), (angle, speed): (f64, f64), life: (f64, f64), size: (f64, f64), color: Rgba, shape: Shape) {
+        let p = Particle {
+            x, y,
+            vx: angle.cos() * speed, vy: angle.sin() * speed,
+            age: 0.0, life: self.rng.range(life.0, life.1),
+            size: self.rng.range(size.0, size.1),
+            color, shape,
+        };
+        if self.pool.len() < POOL_SIZE {
+            self.pool.push(p);
+        } else {
+            self.pool[self.cursor] = p;
+            self.cursor = (self.cursor + 1) % POOL_SIZE;
+        }
+    }
+
+    /// `base` scaled by the intensity, with the fractional part rolled.
+    fn count(&mut self, base: f64) -> usize {
+        let n = (base * self.intensity).max(0.0);
+        n.floor() as usize + usize::from(self.rng.next_f64() < n.fract())
+    }
+
+    pub fn spawn(&mut self, events: &[SimEvent], theme: &Theme) {
+        if self.intensity <= 0.0 { return; }
+        for &ev in events {
+            match ev {
+                SimEvent::Collision { x, y, nx, ny, impulse } => {
+                    // Sparks fly out sideways from the contact, more for harder hits
+                    let n = self.count((impulse / 120.0).clamp(2.0, 16.0));
+                    let tangent = nx.atan2(-ny);
+                    for _ in 0..n {
+                        let side = if self.rng.next_f64() < 0.5 { 0.0 } else { PI };
+                        let a = tangent + side + self.rng.range(-0.6, 0.6);
+                        let v = self.rng.range(120.0, 320.0);
+                        let color = Rgba::hex("#fff3c4").lerp(Rgba::hex("#ffb347"), self.rng.next_f64());
+                        self.emit((x, y), (a, v), (0.25, 0.45), (1.2, 2.0), color, Shape::Spark);
+                    }
+                }
+                SimEvent::Capture { x, y, from } => {
+                    let n = self.count(1.5);
+                    for _ in 0..n {
+                        let a = self.rng.range(0.0, 2.0 * PI);
+                        let v = self.rng.range(20.0, 70.0);
+                        self.emit((x, y), (a, v), (0.35, 0.6), (1.5, 2.5), theme.team(from).cell, Shape::Chip);
+                    }
+                }
+                SimEvent::WallBounce { x, y, nx, ny, speed } => {
+                    let n = self.count((speed / 150.0).clamp(1.0, 4.0));
+                    let out = ny.atan2(nx);
+                    for _ in 0..n {
+                        let a = out + self.rng.range(-1.0, 1.0);
+                        let v = self.rng.range(30.0, 90.0);
+                        self.emit((x, y), (a, v), (0.4, 0.7), (2.0, 3.5), Rgba::hex("#b9ad99"), Shape::Dust);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Age, move and retire particles; `dt` in sim seconds.
+    pub fn update(&mut self, dt: f64) {
+        let damp = (1.0 - DRAG * dt).max(0.0);
+        let mut i = 0;
+        while i < self.pool.len() {
+            let p = &mut self.pool[i];
+            p.age += dt;
+            if p.age >= p.life {
+                self.pool.swap_remove(i);
+                continue;
+            }
+            p.x += p.vx * dt;
+            p.y += p.vy * dt;
+            p.vx *= damp;
+            p.vy *= damp;
+            i += 1;
+        }
+        if self.pool.len() < POOL_SIZE { self.cursor = 0; }
+    }
+
+    pub fn draw(&self, r: &mut dyn Renderer) {
+        for p in &self.pool {
+            let fade = 1.0 - p.age / p.life;
+            match p.shape {
+                Shape::Spark => {
+                    r.set_alpha(fade);
+                    r.stroke_polyline(&[(p.x, p.y), (p.x - p.vx * 0.03, p.y - p.vy * 0.03)], p.color, p.size, None);
+                }
+                Shape::Chip => {
+                    r.set_alpha(fade);
+                    let h = p.size * 0.5;
+                    r.fill_rect(p.x - h, p.y - h, p.size, p.size, p.color);
+                }
+                Shape::Dust => {
+                    // Puffs spread as they fade
+                    r.set_alpha(fade * 0.5);
+                    r.fill_circle(p.x, p.y, p.size * (1.0 + p.age / p.life), &Paint::Solid(p.color));
+                }
+            }
+        }
+        r.set_alpha(1.0);
+    }
+}
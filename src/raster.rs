@@ -0,0 +1,353 @@
+use crate::render::{self, CellView, FlipAnim, Paint, Renderer, Rgba, TextAnchor};
+use crate::sim::Sim;
+use crate::theme::Theme;
+
+/// Software `Renderer` into an RGBA8 buffer, for native snapshots of board states.
+/// Pixel centres are sampled without anti-aliasing, so output is deterministic across
+/// platforms. Text is skipped (there is no font rasteriser).
+pub(crate) struct Framebuffer {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<u8>, // row-major RGBA, straight alpha
+    scale: f64,          // device pixels per CSS pixel
+    view: (f64, f64, f64), // camera zoom and top-left, see `Renderer::set_view`
+    alpha: f64,
+}
+
+impl Framebuffer {
+    /// Transparent buffer of `width`×`height` device pixels; drawing coordinates are
+    /// multiplied by `scale`.
+    pub fn new(width: usize, height: usize, scale: f64) -> Self {
+        Framebuffer { width, height, pixels: vec![0; width * height * 4], scale, view: (1.0, 0.0, 0.0), alpha: 1.0 }
+    }
+
+    /// Source-over blend of `c` (scaled by the global alpha) into device pixel `(x, y)`.
+    fn blend(&mut self, x: usize, y: usize, c: Rgba) {
+        let sa = c.a as f64 / 255.0 * self.alpha;
+        if sa <= 0.0 { return; }
+        let i = (y * self.width + x) * 4;
+        let p = &mut self.pixels[i..i + 4];
+        let da = p[3] as f64 / 255.0;
+        let oa = sa + da * (1.0 - sa);
+        let mix = |s: u8, d: u8| ((s as f64 * sa + d as f64 * da * (1.0 - sa)) / oa).round() as u8;
+        p[0] = mix(c.r, p[0]);
+        p[1] = mix(c.g, p[1]);
+        p[2] = mix(c.b, p[2]);
+        p[3] = (oa * 255.0).round() as u8;
+    }
+
+    /// Device pixels per drawing unit under the current view.
+    fn k(&self) -> f64 { self.scale * self.view.0 }
+
+    /// Device-pixel range whose centres may fall in `[lo, hi]` (drawing units) along an
+    /// axis of `n` pixels whose view origin is `o`.
+    fn span(&self, lo: f64, hi: f64, o: f64, n: usize) -> std::ops::Range<usize> {
+        let k = self.k();
+        let a = ((lo - o) * k - 0.5).ceil().max(0.0) as usize;
+        let b = (((hi - o) * k - 0.5).floor() + 1.0).clamp(0.0, n as f64) as usize;
+        a.min(b)..b
+    }
+
+    /// Centre of device pixel `p` in drawing units, along an axis with view origin `o`.
+    fn center(&self, p: usize, o: f64) -> f64 { (p as f64 + 0.5) / self.k() + o }
+
+    /// Calls `f` with each device pixel and its centre in drawing units, over a bounding box.
+    fn each_in(&mut self, x0: f64, y0: f64, x1: f64, y1: f64, mut f: impl FnMut(&mut Self, usize, usize, f64, f64)) {
+        let (_, ox, oy) = self.view;
+        let (xs, ys) = (self.span(x0, x1, ox, self.width), self.span(y0, y1, oy, self.height));
+        for py in ys {
+            for px in xs.clone() {
+                let (cx, cy) = (self.center(px, ox), self.center(py, oy));
+                f(self, px, py, cx, cy);
+            }
+        }
+    }
+
+    fn paint_at(paint: &Paint, x: f64, y: f64) -> Rgba {
+        match paint {
+            Paint::Solid(c) => *c,
+            Paint::Radial(g) => g.color_at(x, y),
+        }
+    }
+
+    fn stroke_segment(&mut self, a: (f64, f64), b: (f64, f64), color: Rgba, width: f64) {
+        let hw = (width * 0.5).max(0.5 / self.k());
+        let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+        let len2 = dx * dx + dy * dy;
+        self.each_in(a.0.min(b.0) - hw, a.1.min(b.1) - hw, a.0.max(b.0) + hw, a.1.max(b.1) + hw, |fb, px, py, x, y| {
+            let t = if len2 > 0.0 { (((x - a.0) * dx + (y - a.1) * dy) / len2).clamp(0.0, 1.0) } else { 0.0 };
+            let (ex, ey) = (x - (a.0 + t * dx), y - (a.1 + t * dy));
+            if ex * ex + ey * ey <= hw * hw { fb.blend(px, py, color); }
+        });
+    }
+
+    /// PNG encoding (8-bit RGBA, stored deflate blocks).
+    pub fn to_png(&self) -> Vec<u8> {
+        let mut raw = Vec::with_capacity((self.width * 4 + 1) * self.height);
+        for row in self.pixels.chunks(self.width * 4) {
+            raw.push(0); // filter: none
+            raw.extend_from_slice(row);
+        }
+
+        let mut zlib = vec![0x78, 0x01];
+        let mut blocks = raw.chunks(0xffff).peekable();
+        if blocks.peek().is_none() { zlib.extend_from_slice(&[1, 0, 0, 0xff, 0xff]); }
+        while let Some(block) = blocks.next() {
+            let len = block.len() as u16;
+            zlib.push(if blocks.peek().is_none() { 1 } else { 0 });
+            zlib.extend_from_slice(&len.to_le_bytes());
+            zlib.extend_from_slice(&(!len).to_le_bytes());
+            zlib.extend_from_slice(block);
+        }
+        zlib.extend_from_slice(&adler32(&raw).to_be_bytes());
+
+        let mut ihdr = Vec::with_capacity(13);
+        ihdr.extend_from_slice(&(self.width as u32).to_be_bytes());
+        ihdr.extend_from_slice(&(self.height as u32).to_be_bytes());
+        ihdr.extend_from_slice(&[8, 6, 0, 0, 0]); // 8-bit, RGBA, deflate, no filter, no interlace
+
+        let mut out = vec![0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+        for (kind, data) in [(b"IHDR", &ihdr), (b"IDAT", &zlib), (b"IEND", &Vec::new())] {
+            out.extend_from_slice(&(data.len() as u32).to_be_bytes());
+            let start = out.len();
+            out.extend_from_slice(kind);
+            out.extend_from_slice(data);
+            let crc = crc32(&out[start..]);
+            out.extend_from_slice(&crc.to_be_bytes());
+        }
+        out
+    }
+}
+
+/// The settled board (no flip animation) as `draw_board` paints it, at `scale` device
+/// pixels per arena pixel.
+pub(crate) fn snapshot(sim: &Sim, view: CellView, theme: &Theme, scale: f64) -> Framebuffer {
+    let w = (sim.width * scale).round().max(1.0) as usize;
+    let h = (sim.height * scale).round().max(1.0) as usize;
+    let mut fb = Framebuffer::new(w, h, scale);
+    render::draw_board(&mut fb, sim, view, theme, &FlipAnim::OFF);
+    fb
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &b in bytes {
+        crc ^= b as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 { 0xedb8_8320 ^ (crc >> 1) } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+fn adler32(bytes: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for &x in bytes {
+        a = (a + x as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
+
+impl Renderer for Framebuffer {
+    fn set_alpha(&mut self, alpha: f64) { self.alpha = alpha.clamp(0.0, 1.0); }
+
+    fn set_view(&mut self, zoom: f64, x: f64, y: f64) { self.view = (zoom, x, y); }
+
+    fn fill_rect(&mut self, x: f64, y: f64, w: f64, h: f64, color: Rgba) {
+        self.each_in(x, y, x + w, y + h, |fb, px, py, _, _| fb.blend(px, py, color));
+    }
+
+    fn fill_polygon(&mut self, pts: &[(f64, f64)], paint: &Paint) {
+        if pts.len() < 3 { return; }
+        let (y0, y1) = pts.iter().fold((f64::MAX, f64::MIN), |(lo, hi), p| (lo.min(p.1), hi.max(p.1)));
+        let mut xs = Vec::new();
+        let (_, ox, oy) = self.view;
+        for py in self.span(y0, y1, oy, self.height) {
+            // Even-odd scanline at the row's pixel centre
+            let y = self.center(py, oy);
+            xs.clear();
+            for (i, &(ax, ay)) in pts.iter().enumerate() {
+                let (bx, by) = pts[(i + 1) % pts.len()];
+                if (ay <= y) != (by <= y) {
+                    xs.push(ax + (y - ay) / (by - ay) * (bx - ax));
+                }
+            }
+            xs.sort_by(f64::total_cmp);
+            for pair in xs.chunks_exact(2) {
+                for px in self.span(pair[0], pair[1], ox, self.width) {
+                    let x = self.center(px, ox);
+                    self.blend(px, py, Self::paint_at(paint, x, y));
+                }
+            }
+        }
+    }
+
+    fn stroke_polygon(&mut self, pts: &[(f64, f64)], color: Rgba, width: f64) {
+        for (i, &a) in pts.iter().enumerate() {
+            self.stroke_segment(a, pts[(i + 1) % pts.len()], color, width);
+        }
+    }
+
+    fn stroke_polyline(&mut self, pts: &[(f64, f64)], color: Rgba, width: f64, dash: Option<(f64, f64)>) {
+        let Some((on, off)) = dash.filter(|(on, off)| *on > 0.0 && *off >= 0.0) else {
+            for pair in pts.windows(2) { self.stroke_segment(pair[0], pair[1], color, width); }
+            return;
+        };
+        // Walk the path, carrying the dash phase across vertices
+        let (mut phase, mut drawing) = (on, true);
+        for pair in pts.windows(2) {
+            let ((ax, ay), (bx, by)) = (pair[0], pair[1]);
+            let len = ((bx - ax).powi(2) + (by - ay).powi(2)).sqrt();
+            let mut at = 0.0;
+            while at < len {
+                let step = phase.min(len - at);
+                if drawing {
+                    let p = |d: f64| (ax + (bx - ax) * d / len, ay + (by - ay) * d / len);
+                    self.stroke_segment(p(at), p(at + step), color, width);
+                }
+                at += step;
+                phase -= step;
+                if phase <= 0.0 {
+                    drawing = !drawing;
+                    phase = if drawing { on } else { off };
+                }
+            }
+        }
+    }
+
+    fn fill_circle(&mut self, cx: f64, cy: f64, r: f64, paint: &Paint) {
+        self.each_in(cx - r, cy - r, cx + r, cy + r, |fb, px, py, x, y| {
+            if (x - cx).powi(2) + (y - cy).powi(2) <= r * r {
+                fb.blend(px, py, Self::paint_at(paint, x, y));
+            }
+        });
+    }
+
+    fn stroke_circle(&mut self, cx: f64, cy: f64, r: f64, color: Rgba, width: f64) {
+        let hw = (width * 0.5).max(0.5 / self.k());
+        let outer = r + hw;
+        self.each_in(cx - outer, cy - outer, cx + outer, cy + outer, |fb, px, py, x, y| {
+            let d = ((x - cx).powi(2) + (y - cy).powi(2)).sqrt();
+            if (d - r).abs() <= hw { fb.blend(px, py, color); }
+        });
+    }
+
+    fn fill_text(&mut self, _text: &str, _x: f64, _y: f64, _size: f64, _color: Rgba, _anchor: TextAnchor) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sim::{HexColor, Team};
+
+    /// Minimal PNG reader for what `to_png` writes: checks signature, chunk CRCs, stored
+    /// deflate lengths and the Adler-32, then undoes the filter bytes.
+    fn decode_png(png: &[u8]) -> (usize, usize, Vec<u8>) {
+        assert_eq!(&png[..8], &[0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n']);
+        let (mut at, mut ihdr, mut idat) = (8, vec![], vec![]);
+        while at < png.len() {
+            let len = u32::from_be_bytes(png[at..at + 4].try_into().unwrap()) as usize;
+            let body = &png[at + 4..at + 8 + len];
+            let crc = u32::from_be_bytes(png[at + 8 + len..at + 12 + len].try_into().unwrap());
+            assert_eq!(crc32(body), crc);
+            match &body[..4] {
+                b"IHDR" => ihdr = body[4..].to_vec(),
+                b"IDAT" => idat.extend_from_slice(&body[4..]),
+                b"IEND" => assert_eq!(len, 0),
+                other => panic!("unexpected chunk {:?}", other),
+            }
+            at += 12 + len;
+        }
+        let w = u32::from_be_bytes(ihdr[0..4].try_into().unwrap()) as usize;
+        let h = u32::from_be_bytes(ihdr[4..8].try_into().unwrap()) as usize;
+        assert_eq!(&ihdr[8..], &[8, 6, 0, 0, 0]);
+
+        assert_eq!(&idat[..2], &[0x78, 0x01]);
+        let (mut at, mut raw) = (2, vec![]);
+        loop {
+            let last = idat[at] & 1 == 1;
+            assert_eq!(idat[at] >> 1, 0, "stored block expected");
+            let len = u16::from_le_bytes([idat[at + 1], idat[at + 2]]);
+            let nlen = u16::from_le_bytes([idat[at + 3], idat[at + 4]]);
+            assert_eq!(len, !nlen);
+            raw.extend_from_slice(&idat[at + 5..at + 5 + len as usize]);
+            at += 5 + len as usize;
+            if last { break; }
+        }
+        assert_eq!(u32::from_be_bytes(idat[at..at + 4].try_into().unwrap()), adler32(&raw));
+        assert_eq!(at + 4, idat.len());
+
+        let mut pixels = Vec::with_capacity(w * h * 4);
+        for row in raw.chunks(w * 4 + 1) {
+            assert_eq!(row[0], 0);
+            pixels.extend_from_slice(&row[1..]);
+        }
+        (w, h, pixels)
+    }
+
+    fn pixel(fb: &Framebuffer, x: f64, y: f64) -> Rgba {
+        let (px, py) = ((x * fb.scale) as usize, (y * fb.scale) as usize);
+        let p = &fb.pixels[(py * fb.width + px) * 4..][..4];
+        Rgba { r: p[0], g: p[1], b: p[2], a: p[3] }
+    }
+
+    #[test]
+    fn checksums_match_reference_values() {
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+        assert_eq!(adler32(b""), 1);
+    }
+
+    #[test]
+    fn png_round_trips_across_several_deflate_blocks() {
+        // 200×100 RGBA is just over 80 kB of scanlines, so two stored blocks
+        let mut fb = Framebuffer::new(200, 100, 1.0);
+        for (i, p) in fb.pixels.iter_mut().enumerate() { *p = (i * 7 % 251) as u8; }
+        let (w, h, pixels) = decode_png(&fb.to_png());
+        assert_eq!((w, h), (200, 100));
+        assert!(pixels == fb.pixels);
+
+        let empty = Framebuffer::new(1, 1, 1.0);
+        assert_eq!(decode_png(&empty.to_png()).2, vec![0; 4]);
+    }
+
+    #[test]
+    fn snapshot_paints_the_starting_board() {
+        let mut sim = Sim::new(320.0, 200.0, 1);
+        let id = sim.add_ball(Team::White, (160.0, 100.0), (1.0, 0.0), 10.0);
+        let theme = Theme::classic();
+        let fb = snapshot(&sim, CellView::Owner, &theme, 2.0);
+        assert_eq!((fb.width, fb.height), (640, 400));
+
+        let (white, black) = (&sim.grid.cells[0], sim.grid.cells.last().unwrap());
+        assert!(white.color == HexColor::White && black.color == HexColor::Black);
+        assert_eq!(pixel(&fb, white.cx, white.cy), theme.owner(HexColor::White).cell);
+        assert_eq!(pixel(&fb, black.cx, black.cy), theme.owner(HexColor::Black).cell);
+
+        // Ball rim is close to the last gradient stop; the specular dot is lighter than the rim
+        let b = sim.balls.iter().find(|b| b.id == id).unwrap();
+        let rim = pixel(&fb, b.x + b.radius - 0.5, b.y);
+        assert!(rim.r.abs_diff(theme.team(Team::White).ball[2].r) <= 2);
+        let dot = pixel(&fb, b.x - b.radius * 0.45, b.y - b.radius * 0.45);
+        assert!(dot.r > rim.r);
+
+        let (w, h, pixels) = decode_png(&fb.to_png());
+        assert_eq!((w, h), (fb.width, fb.height));
+        assert!(pixels == fb.pixels);
+    }
+
+    #[test]
+    fn snapshot_is_stable_for_a_seed() {
+        let run = || {
+            let mut sim = Sim::new(240.0, 160.0, 11);
+            sim.spawn_balls_per_team(3);
+            for _ in 0..90 { sim.step(1.0 / 60.0); }
+            snapshot(&sim, CellView::Owner, &Theme::classic(), 1.0)
+        };
+        let (a, b) = (run(), run());
+        assert!(a.pixels == b.pixels);
+        // Reference image; update after an intended change to the board or its drawing
+        assert_eq!(crc32(&a.pixels), 0x44f0_8099);
+    }
+}
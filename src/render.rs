@@ -0,0 +1,477 @@
+use std::f64::consts::PI;
+
+use wasm_bindgen::JsValue;
+use web_sys::CanvasRenderingContext2d;
+
+use crate::sim::{hex_corners, Cell, Grid, HexColor, PowerUpKind, Sim, POWERUP_RADIUS};
+use crate::theme::Theme;
+
+/// Straight (non-premultiplied) 8-bit colour.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) struct Rgba {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+impl Rgba {
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Rgba { Rgba { r, g, b, a: 255 } }
+
+    /// `#rgb` or `#rrggbb`; anything else is opaque black.
+    pub fn hex(s: &str) -> Rgba {
+        let s = s.trim_start_matches('#');
+        let digit = |i: usize, n: usize| u8::from_str_radix(&s[i..i + n], 16).unwrap_or(0);
+        match s.len() {
+            3 => Rgba::rgb(digit(0, 1) * 17, digit(1, 1) * 17, digit(2, 1) * 17),
+            6 => Rgba::rgb(digit(0, 2), digit(2, 2), digit(4, 2)),
+            _ => Rgba::rgb(0, 0, 0),
+        }
+    }
+
+    /// `h` in degrees, `s` and `l` in 0..=1.
+    pub fn hsl(h: f64, s: f64, l: f64) -> Rgba {
+        let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
+        let hp = h.rem_euclid(360.0) / 60.0;
+        let x = c * (1.0 - (hp % 2.0 - 1.0).abs());
+        let (r, g, b) = match hp as u32 {
+            0 => (c, x, 0.0),
+            1 => (x, c, 0.0),
+            2 => (0.0, c, x),
+            3 => (0.0, x, c),
+            4 => (x, 0.0, c),
+            _ => (c, 0.0, x),
+        };
+        let m = l - c * 0.5;
+        let to = |v: f64| ((v + m) * 255.0).round().clamp(0.0, 255.0) as u8;
+        Rgba::rgb(to(r), to(g), to(b))
+    }
+
+    pub fn css(self) -> String {
+        if self.a == 255 {
+            format!("#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+        } else {
+            format!("rgba({},{},{},{:.3})", self.r, self.g, self.b, self.a as f64 / 255.0)
+        }
+    }
+
+    pub fn lerp(self, other: Rgba, t: f64) -> Rgba {
+        let mix = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * t).round() as u8;
+        Rgba { r: mix(self.r, other.r), g: mix(self.g, other.g), b: mix(self.b, other.b), a: mix(self.a, other.a) }
+    }
+}
+
+/// Two-circle radial gradient with the same semantics as canvas `createRadialGradient`.
+#[derive(Clone)]
+pub(crate) struct RadialGradient {
+    pub x0: f64, pub y0: f64, pub r0: f64,
+    pub x1: f64, pub y1: f64, pub r1: f64,
+    pub stops: Vec<(f64, Rgba)>, // ascending offsets in 0..=1
+}
+
+impl RadialGradient {
+    /// Colour at `(x, y)`: the largest `t` whose interpolated circle passes through the
+    /// point, clamped to the stop range.
+    pub fn color_at(&self, x: f64, y: f64) -> Rgba {
+        let (cdx, cdy, dr) = (self.x1 - self.x0, self.y1 - self.y0, self.r1 - self.r0);
+        let (pdx, pdy) = (x - self.x0, y - self.y0);
+        let a = cdx * cdx + cdy * cdy - dr * dr;
+        let b = pdx * cdx + pdy * cdy + self.r0 * dr;
+        let c = pdx * pdx + pdy * pdy - self.r0 * self.r0;
+        let t = if a.abs() < 1e-9 {
+            if b.abs() < 1e-9 { 0.0 } else { c / (2.0 * b) }
+        } else {
+            let disc = (b * b - a * c).max(0.0).sqrt();
+            let (t1, t2) = ((b + disc) / a, (b - disc) / a);
+            let r_ok = |t: f64| self.r0 + t * dr >= 0.0;
+            if r_ok(t1.max(t2)) { t1.max(t2) } else { t1.min(t2) }
+        };
+        self.sample(t.clamp(0.0, 1.0))
+    }
+
+    fn sample(&self, t: f64) -> Rgba {
+        let Some(&(first_at, first)) = self.stops.first() else { return Rgba::rgb(0, 0, 0) };
+        if t <= first_at { return first; }
+        for pair in self.stops.windows(2) {
+            let ((a_at, a), (b_at, b)) = (pair[0], pair[1]);
+            if t <= b_at {
+                let span = b_at - a_at;
+                return if span <= 0.0 { b } else { a.lerp(b, (t - a_at) / span) };
+            }
+        }
+        self.stops[self.stops.len() - 1].1
+    }
+}
+
+#[derive(Clone)]
+pub(crate) enum Paint {
+    Solid(Rgba),
+    Radial(RadialGradient),
+}
+
+/// Where a text label sits relative to its anchor point.
+#[derive(Clone, Copy)]
+pub(crate) enum TextAnchor { Center, TopLeft, TopRight }
+
+/// The drawing primitives the board needs. Coordinates are CSS pixels, or world units
+/// after `set_view`; backends map them to device pixels. `set_alpha` multiplies
+/// everything drawn until it is reset to 1.
+pub(crate) trait Renderer {
+    fn set_alpha(&mut self, alpha: f64);
+    /// Draw at `zoom` with world point `(x, y)` at the top-left; `(1, 0, 0)` is CSS pixels.
+    fn set_view(&mut self, zoom: f64, x: f64, y: f64);
+    fn fill_rect(&mut self, x: f64, y: f64, w: f64, h: f64, color: Rgba);
+    fn fill_polygon(&mut self, pts: &[(f64, f64)], paint: &Paint);
+    /// Closed outline.
+    fn stroke_polygon(&mut self, pts: &[(f64, f64)], color: Rgba, width: f64);
+    /// Open path; `dash` is `(on, off)` lengths.
+    fn stroke_polyline(&mut self, pts: &[(f64, f64)], color: Rgba, width: f64, dash: Option<(f64, f64)>);
+    fn fill_circle(&mut self, cx: f64, cy: f64, r: f64, paint: &Paint);
+    fn stroke_circle(&mut self, cx: f64, cy: f64, r: f64, color: Rgba, width: f64);
+    /// Bold sans-serif label. Backends without a font may skip it.
+    fn fill_text(&mut self, text: &str, x: f64, y: f64, size: f64, color: Rgba, anchor: TextAnchor);
+
+    fn fill_stroke_polygon(&mut self, pts: &[(f64, f64)], fill: &Paint, stroke: Rgba, width: f64) {
+        self.fill_polygon(pts, fill);
+        self.stroke_polygon(pts, stroke, width);
+    }
+}
+
+/// `Renderer` over a browser 2D context whose canvas is `dpr` device pixels per CSS pixel.
+pub(crate) struct CanvasRenderer<'a> {
+    ctx: &'a CanvasRenderingContext2d,
+    dpr: f64,
+}
+
+impl<'a> CanvasRenderer<'a> {
+    pub fn new(ctx: &'a CanvasRenderingContext2d, dpr: f64) -> Self {
+        ctx.set_line_join("round");
+        ctx.set_line_cap("round");
+        let mut r = CanvasRenderer { ctx, dpr };
+        r.set_view(1.0, 0.0, 0.0);
+        r
+    }
+
+    fn set_fill(&self, paint: &Paint) {
+        match paint {
+            Paint::Solid(c) => self.ctx.set_fill_style_str(&c.css()),
+            Paint::Radial(g) => {
+                let grad = self.ctx.create_radial_gradient(g.x0, g.y0, g.r0, g.x1, g.y1, g.r1).unwrap();
+                for (at, c) in &g.stops {
+                    let _ = grad.add_color_stop(*at as f32, &c.css());
+                }
+                self.ctx.set_fill_style_canvas_gradient(&grad);
+            }
+        }
+    }
+
+    fn set_stroke(&self, color: Rgba, width: f64) {
+        self.ctx.set_stroke_style_str(&color.css());
+        self.ctx.set_line_width(width);
+    }
+
+    fn path(&self, pts: &[(f64, f64)]) {
+        self.ctx.begin_path();
+        for (i, &(x, y)) in pts.iter().enumerate() {
+            if i == 0 { self.ctx.move_to(x, y); } else { self.ctx.line_to(x, y); }
+        }
+    }
+
+    fn circle(&self, cx: f64, cy: f64, r: f64) {
+        self.ctx.begin_path();
+        let _ = self.ctx.arc(cx, cy, r, 0.0, PI * 2.0);
+    }
+}
+
+impl Renderer for CanvasRenderer<'_> {
+    fn set_alpha(&mut self, alpha: f64) { self.ctx.set_global_alpha(alpha.clamp(0.0, 1.0)); }
+
+    fn set_view(&mut self, zoom: f64, x: f64, y: f64) {
+        let k = self.dpr * zoom;
+        let _ = self.ctx.set_transform(k, 0.0, 0.0, k, -k * x, -k * y);
+    }
+
+    fn fill_rect(&mut self, x: f64, y: f64, w: f64, h: f64, color: Rgba) {
+        self.ctx.set_fill_style_str(&color.css());
+        self.ctx.fill_rect(x, y, w, h);
+    }
+
+    fn fill_polygon(&mut self, pts: &[(f64, f64)], paint: &Paint) {
+        self.set_fill(paint);
+        self.path(pts);
+        self.ctx.close_path();
+        self.ctx.fill();
+    }
+
+    fn stroke_polygon(&mut self, pts: &[(f64, f64)], color: Rgba, width: f64) {
+        self.set_stroke(color, width);
+        self.path(pts);
+        self.ctx.close_path();
+        self.ctx.stroke();
+    }
+
+    fn stroke_polyline(&mut self, pts: &[(f64, f64)], color: Rgba, width: f64, dash: Option<(f64, f64)>) {
+        self.set_stroke(color, width);
+        if let Some((on, off)) = dash {
+            let _ = self.ctx.set_line_dash(&js_sys::Array::of2(&JsValue::from_f64(on), &JsValue::from_f64(off)));
+        }
+        self.path(pts);
+        self.ctx.stroke();
+        if dash.is_some() {
+            let _ = self.ctx.set_line_dash(&js_sys::Array::new());
+        }
+    }
+
+    fn fill_circle(&mut self, cx: f64, cy: f64, r: f64, paint: &Paint) {
+        self.set_fill(paint);
+        self.circle(cx, cy, r);
+        self.ctx.fill();
+    }
+
+    fn stroke_circle(&mut self, cx: f64, cy: f64, r: f64, color: Rgba, width: f64) {
+        self.set_stroke(color, width);
+        self.circle(cx, cy, r);
+        self.ctx.stroke();
+    }
+
+    fn fill_text(&mut self, text: &str, x: f64, y: f64, size: f64, color: Rgba, anchor: TextAnchor) {
+        let (align, baseline) = match anchor {
+            TextAnchor::Center => ("center", "middle"),
+            TextAnchor::TopLeft => ("left", "top"),
+            TextAnchor::TopRight => ("right", "top"),
+        };
+        self.ctx.set_font(&format!("bold {}px sans-serif", size));
+        self.ctx.set_text_align(align);
+        self.ctx.set_text_baseline(baseline);
+        self.ctx.set_fill_style_str(&color.css());
+        let _ = self.ctx.fill_text(text, x, y);
+    }
+
+    fn fill_stroke_polygon(&mut self, pts: &[(f64, f64)], fill: &Paint, stroke: Rgba, width: f64) {
+        // One path for both, as this runs for every hex every frame
+        self.set_fill(fill);
+        self.set_stroke(stroke, width);
+        self.path(pts);
+        self.ctx.close_path();
+        self.ctx.fill();
+        self.ctx.stroke();
+    }
+}
+
+/// How hexes are filled.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CellView { Owner, Heat }
+
+/// Heat ramp for a flip count relative to the busiest hex: blue (quiet) to red (contested).
+fn heat_color(flips: u32, max_flips: u32) -> Rgba {
+    if flips == 0 { return Rgba::hex("#15151c"); }
+    let t = (1.0 + flips as f64).ln() / (1.0 + max_flips.max(1) as f64).ln();
+    Rgba::hsl(240.0 * (1.0 - t), 0.85, 0.30 + 0.25 * t)
+}
+
+/// How a hex animates when it changes owner.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum FlipStyle { None, Fade, Pop, Rotate }
+
+/// Flip animation settings. Purely visual: progress comes from `Cell::since`, the sim time
+/// of the last flip, so the simulation never waits on it. `duration` is in sim seconds, so
+/// animations follow the speed multiplier and freeze while stopped.
+#[derive(Clone, Copy)]
+pub(crate) struct FlipAnim {
+    pub style: FlipStyle,
+    pub duration: f64,
+}
+
+impl FlipAnim {
+    pub const OFF: FlipAnim = FlipAnim { style: FlipStyle::None, duration: 0.0 };
+
+    /// 0..1 through the animation, or `None` once settled (or never flipped).
+    fn progress(&self, cell: &Cell, now: f64) -> Option<f64> {
+        if self.style == FlipStyle::None || self.duration <= 0.0 || cell.flips == 0 { return None; }
+        let t = (now - cell.since) / self.duration;
+        (0.0..1.0).contains(&t).then_some(t)
+    }
+}
+
+impl Default for FlipAnim {
+    fn default() -> Self { FlipAnim { style: FlipStyle::Fade, duration: 0.3 } }
+}
+
+/// Overshoots past 1 before settling, for the pop.
+fn ease_out_back(t: f64) -> f64 {
+    const C1: f64 = 1.70158;
+    let u = t - 1.0;
+    1.0 + (C1 + 1.0) * u * u * u + C1 * u * u
+}
+
+/// Hex corners scaled by `sx` horizontally and `sy` vertically about the centre.
+fn scaled_hex(cx: f64, cy: f64, r: f64, sx: f64, sy: f64) -> [(f64, f64); 6] {
+    hex_corners(cx, cy, r).map(|(x, y)| (cx + (x - cx) * sx, cy + (y - cy) * sy))
+}
+
+fn draw_hex(r: &mut dyn Renderer, pts: &[(f64, f64)], fill: Rgba, line: Rgba, line_width: f64) {
+    if line_width > 0.0 {
+        r.fill_stroke_polygon(pts, &Paint::Solid(fill), line, line_width);
+    } else {
+        r.fill_polygon(pts, &Paint::Solid(fill));
+    }
+}
+
+impl Grid {
+    pub(crate) fn draw(&self, r: &mut dyn Renderer, view: CellView, theme: &Theme, flip: &FlipAnim, now: f64) {
+        let max_flips = self.cells.iter().map(|c| c.flips).max().unwrap_or(0);
+        let lw = theme.line_width;
+        for cell in &self.cells {
+            // Cells on a wrapped seam are drawn again on the far side
+            for (cx, cy) in seam_images(cell.cx, cell.cy, self.r, self.period) {
+                let owner = theme.owner(cell.color);
+                let pts = hex_corners(cx, cy, self.r);
+                let t = match view {
+                    CellView::Owner => flip.progress(cell, now),
+                    CellView::Heat => {
+                        draw_hex(r, &pts, heat_color(cell.flips, max_flips), owner.line, lw);
+                        continue;
+                    }
+                };
+                let Some(t) = t else {
+                    draw_hex(r, &pts, owner.cell, owner.line, lw);
+                    continue;
+                };
+
+                let prev = theme.owner(match cell.color { HexColor::White => HexColor::Black, HexColor::Black => HexColor::White });
+                match flip.style {
+                    FlipStyle::Fade | FlipStyle::None => {
+                        draw_hex(r, &pts, prev.cell.lerp(owner.cell, t), prev.line.lerp(owner.line, t), lw);
+                    }
+                    FlipStyle::Pop => {
+                        draw_hex(r, &pts, prev.cell, prev.line, lw);
+                        let s = ease_out_back(t);
+                        draw_hex(r, &scaled_hex(cx, cy, self.r, s, s), owner.cell, owner.line, lw);
+                    }
+                    FlipStyle::Rotate => {
+                        // Turn about the vertical axis: old face shrinks to edge-on, new face opens
+                        let face = if t < 0.5 { prev } else { owner };
+                        r.fill_polygon(&pts, &Paint::Solid(theme.background));
+                        let sx = (PI * t).cos().abs();
+                        draw_hex(r, &scaled_hex(cx, cy, self.r, sx, 1.0), face.cell, face.line, lw);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Background, hexes, power-ups and balls: everything but effects and overlays.
+pub(crate) fn draw_board(r: &mut dyn Renderer, sim: &Sim, view: CellView, theme: &Theme, flip: &FlipAnim) {
+    draw_arena(r, sim, view, theme, flip);
+    draw_balls(r, sim, theme);
+}
+
+/// Background, hexes and power-ups.
+pub(crate) fn draw_arena(r: &mut dyn Renderer, sim: &Sim, view: CellView, theme: &Theme, flip: &FlipAnim) {
+    r.fill_rect(0.0, 0.0, sim.width, sim.height, theme.background);
+
+    sim.grid.draw(r, view, theme, flip, sim.clock);
+
+    // Power-up pickups
+    for p in &sim.powerups {
+        let (fill, glyph) = p.kind.icon();
+        r.fill_circle(p.x, p.y, POWERUP_RADIUS, &Paint::Solid(Rgba::hex(fill)));
+        r.stroke_circle(p.x, p.y, POWERUP_RADIUS, theme.background, 2.0);
+        r.fill_text(glyph, p.x, p.y + 0.5, 11.0, theme.background, TextAnchor::Center);
+    }
+}
+
+const FIELD_COLOR: &str = "#0ea5e9";
+const FIELD_SPACING: f64 = 36.0; // px between field arrows
+
+/// Faint arrows sampling each force field on a lattice, plus its boundary ring.
+pub(crate) fn draw_fields(r: &mut dyn Renderer, sim: &Sim) {
+    let color = Rgba::hex(FIELD_COLOR);
+    let period = sim.wrap.then_some((sim.width, sim.height));
+    r.set_alpha(0.55);
+    for f in &sim.fields {
+        // Lattice over the field's box, kept inside the world; a box crossing a seam of a
+        // wrapped arena reappears on the far side, so it takes the whole axis
+        let clip = |lo: f64, hi: f64, size: f64| {
+            if f.radius <= 0.0 || (period.is_some() && (lo < 0.0 || hi > size)) { (0.0, size) } else { (lo.max(0.0), hi.min(size)) }
+        };
+        let (x0, x1) = clip(f.x - f.radius, f.x + f.radius, sim.width);
+        let (y0, y1) = clip(f.y - f.radius, f.y + f.radius, sim.height);
+        let scale = FIELD_SPACING * 0.5 / f.strength.abs().max(1e-6); // peak arrow is half the spacing
+        let mut y = (y0 / FIELD_SPACING).ceil() * FIELD_SPACING;
+        while y <= y1 {
+            let mut x = (x0 / FIELD_SPACING).ceil() * FIELD_SPACING;
+            while x <= x1 {
+                let (ax, ay) = f.accel_at(x, y, period);
+                if ax.hypot(ay) * scale > 1.0 {
+                    let tip = (x + ax * scale, y + ay * scale);
+                    r.stroke_polyline(&[(x, y), tip], color, 2.0, None);
+                    r.fill_circle(tip.0, tip.1, 2.5, &Paint::Solid(color));
+                }
+                x += FIELD_SPACING;
+            }
+            y += FIELD_SPACING;
+        }
+        if f.radius > 0.0 {
+            r.stroke_polyline(&circle_points(f.x, f.y, f.radius, 48), color, 1.0, Some((6.0, 6.0)));
+        }
+    }
+    r.set_alpha(1.0);
+}
+
+fn circle_points(cx: f64, cy: f64, radius: f64, n: usize) -> Vec<(f64, f64)> {
+    (0..=n).map(|i| {
+        let a = i as f64 / n as f64 * 2.0 * PI;
+        (cx + radius * a.cos(), cy + radius * a.sin())
+    }).collect()
+}
+
+/// Where to draw something of radius `reach` at `(x, y)`: once, plus its images across
+/// each seam it straddles when the arena wraps.
+pub(crate) fn wrap_images(sim: &Sim, x: f64, y: f64, reach: f64) -> Vec<(f64, f64)> {
+    seam_images(x, y, reach, sim.wrap.then_some((sim.width, sim.height)))
+}
+
+/// `wrap_images` for a world that repeats every `period`, if any.
+pub(crate) fn seam_images(x: f64, y: f64, reach: f64, period: Option<(f64, f64)>) -> Vec<(f64, f64)> {
+    let Some((w, h)) = period else { return vec![(x, y)] };
+    let shifts = |p: f64, size: f64| {
+        let mut v = vec![0.0];
+        if p - reach < 0.0 { v.push(size); }
+        if p + reach > size { v.push(-size); }
+        v
+    };
+    let (xs, ys) = (shifts(x, w), shifts(y, h));
+    ys.iter().flat_map(|dy| xs.iter().map(move |dx| (x + dx, y + dy))).collect()
+}
+
+/// Glossy balls with player and shield rings.
+pub(crate) fn draw_balls(r: &mut dyn Renderer, sim: &Sim, theme: &Theme) {
+    for b in &sim.balls {
+        let size = b.size();
+        let palette = theme.team(b.team);
+        for (x, y) in wrap_images(sim, b.x, b.y, size + 8.0) {
+            let grad = RadialGradient {
+                x0: x - size * 0.4, y0: y - size * 0.4, r0: size * 0.05,
+                x1: x, y1: y, r1: size,
+                stops: vec![(0.0, palette.ball[0]), (0.5, palette.ball[1]), (1.0, palette.ball[2])],
+            };
+            r.fill_circle(x, y, size, &Paint::Radial(grad));
+
+            // specular dot
+            let (dot_x, dot_y, dot_r) = (x - size * 0.45, y - size * 0.45, (size * 0.28).max(0.8));
+            r.set_alpha(0.55);
+            r.fill_circle(dot_x, dot_y, dot_r, &Paint::Solid(Rgba::hex("#ffffff")));
+            r.set_alpha(1.0);
+            r.stroke_circle(dot_x, dot_y, dot_r, palette.outline, 1.0);
+
+            if b.player {
+                r.stroke_circle(x, y, size + 6.0, Rgba::hex("#2563eb"), 2.0);
+            }
+            if b.shielded() {
+                r.stroke_circle(x, y, size + 3.0, Rgba::hex(PowerUpKind::Shield.icon().0), 2.0);
+            }
+        }
+    }
+}
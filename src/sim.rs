@@ -0,0 +1,1332 @@
+use std::f64::consts::PI;
+
+use crate::ai::{ArenaView, BallController};
+use crate::stats::{BallStats, ShareSeries, TeamStats};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Team { Black, White }
+
+impl Team {
+    pub fn index(self) -> usize { match self { Team::White => 0, Team::Black => 1 } }
+}
+
+/// Ball archetypes a team can mix at spawn time.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum BallClass { Standard, Heavy, Light }
+
+impl BallClass {
+    /// (radius, mass, speed) multipliers relative to a standard ball.
+    fn profile(self) -> (f64, f64, f64) {
+        match self {
+            BallClass::Standard => (1.0, 1.0, 1.0),
+            BallClass::Heavy => (1.35, 2.5, 0.7),
+            BallClass::Light => (0.75, 0.45, 1.35),
+        }
+    }
+}
+
+/// How many of a team's balls spawn heavy/slow or light/fast; the rest are standard.
+#[derive(Clone, Copy, Default)]
+pub(crate) struct SpawnMix {
+    pub heavy: u32,
+    pub light: u32,
+}
+
+impl SpawnMix {
+    fn class_for(&self, i: u32) -> BallClass {
+        if i < self.heavy { BallClass::Heavy }
+        else if i < self.heavy + self.light { BallClass::Light }
+        else { BallClass::Standard }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Formation { Line, Wedge }
+
+/// Where balls start when a team is (re)spawned.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SpawnLayout {
+    /// White at random in the left quarter, black in the right quarter, each aimed roughly at the other.
+    Sides,
+    /// White as in `Sides`; black is its exact point reflection through the centre.
+    Mirrored,
+    /// Fixed shape for white, point-reflected for black.
+    Formation(Formation),
+    /// Random inside the team's `Sim::spawn_zones`, any heading; `Sides` for a team without zones.
+    Zones,
+    /// Anywhere in the arena, any heading.
+    Random,
+}
+
+impl SpawnLayout {
+    pub const NAMES: [&'static str; 6] = ["sides", "mirrored", "line", "wedge", "zones", "random"];
+
+    pub fn parse(name: &str) -> Option<SpawnLayout> {
+        match name.to_ascii_lowercase().as_str() {
+            "sides" => Some(SpawnLayout::Sides),
+            "mirrored" | "mirror" => Some(SpawnLayout::Mirrored),
+            "line" => Some(SpawnLayout::Formation(Formation::Line)),
+            "wedge" => Some(SpawnLayout::Formation(Formation::Wedge)),
+            "zones" => Some(SpawnLayout::Zones),
+            "random" => Some(SpawnLayout::Random),
+            _ => None,
+        }
+    }
+}
+
+/// Per-team adjustments for balance experiments; the default changes nothing.
+#[derive(Clone, Copy)]
+pub(crate) struct Handicap {
+    pub extra_balls: i32, // added to `balls_per_team`; may be negative
+    pub speed: f64,       // spawn speed multiplier
+    pub radius: f64,      // ball radius multiplier
+    pub claim: f64,       // claim radius as a multiple of the ball's size
+    pub max_speed: f64,   // cruising speed cap, px/s
+}
+
+impl Default for Handicap {
+    fn default() -> Self {
+        Handicap { extra_balls: 0, speed: 1.0, radius: 1.0, claim: 1.0, max_speed: MAX_BASE_SPEED }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub(crate) struct Ball {
+    pub id: u32, // unique within a sim, never reused
+    pub x: f64, pub y: f64,
+    pub vx: f64, pub vy: f64,
+    pub team: Team,
+    pub radius: f64,
+    pub mass: f64,
+    pub base_speed: f64,
+    pub max_speed: f64, // cap on `base_speed`
+    pub last_bounce_ts: f64, // sim clock, seconds
+    pub effects: Effects,
+    pub player: bool,
+    pub stats: BallStats,
+    pub stuck: f64, // seconds left pinned to a sticky wall
+}
+
+/// Steering input for a team's player-controlled ball.
+#[derive(Clone, Copy, Default)]
+pub(crate) struct PlayerSteer {
+    pub enabled: bool,
+    pub dir: (f64, f64),              // keyboard direction, |dir| <= 1
+    pub target: Option<(f64, f64)>,   // pointer target; wins over `dir`
+}
+
+/// Max steering acceleration (px/s^2) applied to a player- or AI-controlled ball.
+const STEER_ACCEL: f64 = 900.0;
+
+/// Remaining seconds (simulation time) of each timed power-up on a ball.
+#[derive(Clone, Copy, Default)]
+pub(crate) struct Effects {
+    pub speed: f64,
+    pub grow: f64,
+    pub shield: f64,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PowerUpKind { Speed, Grow, PaintBomb, Shield, Split }
+
+const POWERUP_KINDS: [PowerUpKind; 5] =
+    [PowerUpKind::Speed, PowerUpKind::Grow, PowerUpKind::PaintBomb, PowerUpKind::Shield, PowerUpKind::Split];
+
+impl PowerUpKind {
+    pub fn index(self) -> usize { POWERUP_KINDS.iter().position(|&k| k == self).unwrap_or(0) }
+
+    pub fn parse(name: &str) -> Option<PowerUpKind> {
+        match name.to_ascii_lowercase().as_str() {
+            "speed" => Some(PowerUpKind::Speed),
+            "grow" => Some(PowerUpKind::Grow),
+            "paint" | "paint_bomb" | "bomb" => Some(PowerUpKind::PaintBomb),
+            "shield" => Some(PowerUpKind::Shield),
+            "split" => Some(PowerUpKind::Split),
+            _ => None,
+        }
+    }
+
+    /// (icon fill, glyph) used by `render`.
+    pub fn icon(self) -> (&'static str, &'static str) {
+        match self {
+            PowerUpKind::Speed => ("#f5c518", "\u{26a1}"),
+            PowerUpKind::Grow => ("#3fb950", "+"),
+            PowerUpKind::PaintBomb => ("#e5534b", "\u{2739}"),
+            PowerUpKind::Shield => ("#539bf5", "\u{25c6}"),
+            PowerUpKind::Split => ("#b083f0", "\u{00d7}2"),
+        }
+    }
+}
+
+pub(crate) struct PowerUp {
+    pub kind: PowerUpKind,
+    pub x: f64, pub y: f64,
+}
+
+/// Spawn rules for power-up pickups.
+pub(crate) struct PowerUpConfig {
+    pub enabled: bool,
+    pub interval: f64,      // seconds between spawns
+    pub max_active: usize,  // pickups on the board at once
+    pub duration: f64,      // seconds for speed / grow / shield
+    pub kinds: [bool; 5],   // indexed by `PowerUpKind::index`
+    pub spawn_hexes: Vec<(usize, usize)>, // (col,row); empty => random hex
+}
+
+impl Default for PowerUpConfig {
+    fn default() -> Self {
+        PowerUpConfig {
+            enabled: false,
+            interval: 4.0,
+            max_active: 3,
+            duration: 6.0,
+            kinds: [true; 5],
+            spawn_hexes: vec![],
+        }
+    }
+}
+
+pub(crate) const POWERUP_RADIUS: f64 = 9.0;
+const SPEED_BURST: f64 = 1.6;
+const GROW_FACTOR: f64 = 1.5;
+const PAINT_BOMB_HEXES: f64 = 6.0; // bomb radius in hex radii
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum HexColor { Black, White }
+
+pub(crate) struct Cell {
+    pub cx: f64, pub cy: f64,
+    pub color: HexColor,
+    pub flips: u32,      // times claimed this match
+    pub held: [f64; 2],  // closed ownership spans, seconds, by `Team::index`
+    pub since: f64,      // sim clock when the current owner took it
+}
+
+impl Cell {
+    fn owner(&self) -> Team {
+        match self.color { HexColor::White => Team::White, HexColor::Black => Team::Black }
+    }
+
+    /// Seconds held by (white, black) up to `now`, including the open span.
+    pub fn held_until(&self, now: f64) -> [f64; 2] {
+        let mut held = self.held;
+        held[self.owner().index()] += (now - self.since).max(0.0);
+        held
+    }
+}
+
+/// Something the renderer may want to show, recorded during one `Sim::step`.
+#[derive(Clone, Copy)]
+pub(crate) enum SimEvent {
+    /// Ball-ball impact at the contact point; `(nx, ny)` runs from the first ball to the
+    /// second and `impulse` is the normal impulse magnitude.
+    Collision { x: f64, y: f64, nx: f64, ny: f64, impulse: f64 },
+    /// A hex at `(x, y)` was taken from `from`.
+    Capture { x: f64, y: f64, from: Team },
+    /// A ball hit a wall at `(x, y)`; `(nx, ny)` points into the arena.
+    WallBounce { x: f64, y: f64, nx: f64, ny: f64, speed: f64 },
+}
+
+/// What an arena edge does to a ball that hits it.
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum WallKind {
+    /// Perfect mirror.
+    Elastic,
+    /// Mirror that keeps this fraction (0..=1) of the ball's cruising speed, down to a floor.
+    Damped(f64),
+    /// Mirror plus a random turn of up to this many radians, always back into the arena.
+    Scatter(f64),
+    /// Holds the ball against the wall for this many seconds, then releases it mirrored.
+    Sticky(f64),
+    /// This team's balls pass through and come back on the opposite edge; the enemy bounces.
+    Owned(Team),
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum FieldKind {
+    /// Pulls toward the centre (pushes away with negative strength), fading out at the radius.
+    Point,
+    /// Uniform push along this angle (radians) anywhere inside the radius.
+    Wind(f64),
+    /// Swirls around the centre, clockwise on screen for positive strength, fading out at the radius.
+    Vortex,
+}
+
+/// A force emitter; `strength` is its peak acceleration in px/s^2 and a `radius` of 0
+/// covers the whole arena.
+#[derive(Clone, Copy)]
+pub(crate) struct ForceField {
+    pub kind: FieldKind,
+    pub x: f64, pub y: f64,
+    pub radius: f64,
+    pub strength: f64,
+}
+
+impl ForceField {
+    /// Acceleration at `(x, y)`; `period` as for `wrap_delta`.
+    pub fn accel_at(&self, x: f64, y: f64, period: Option<(f64, f64)>) -> (f64, f64) {
+        let (dx, dy) = wrap_delta(self.x - x, self.y - y, period);
+        let d = dx.hypot(dy);
+        let falloff = if self.radius > 0.0 { 1.0 - d / self.radius } else { 1.0 };
+        if falloff <= 0.0 { return (0.0, 0.0); }
+        match self.kind {
+            FieldKind::Wind(ang) => (ang.cos() * self.strength, ang.sin() * self.strength),
+            _ if d < 1e-6 => (0.0, 0.0),
+            FieldKind::Point => (dx / d * self.strength * falloff, dy / d * self.strength * falloff),
+            FieldKind::Vortex => (dy / d * self.strength * falloff, -dx / d * self.strength * falloff),
+        }
+    }
+}
+
+/// Edge names in `Sim::walls` order.
+pub(crate) const EDGE_NAMES: [&str; 4] = ["left", "right", "top", "bottom"];
+
+/// Outcome of one `Grid::flip_disc` call.
+pub(crate) struct Claim {
+    pub white: usize,                 // points awarded to white
+    pub black: usize,                 // points awarded to black
+    pub stolen: usize,                // flipped hexes the other team had claimed this match
+    pub normal: Option<(f64, f64)>,   // bounce normal, away from the flipped hexes
+}
+
+pub(crate) struct Grid {
+    pub cells: Vec<Cell>,
+    pub cols: usize,
+    pub rows: usize,
+    pub r: f64,
+    pub period: Option<(f64, f64)>, // world size when the edges wrap; distances go the short way round
+}
+
+impl Ball {
+    /// Current physical radius, including an active grow power-up.
+    pub fn size(&self) -> f64 {
+        if self.effects.grow > 0.0 { self.radius * GROW_FACTOR } else { self.radius }
+    }
+
+    pub fn shielded(&self) -> bool { self.effects.shield > 0.0 }
+
+    fn maintain_speed(&mut self) {
+        let mag = (self.vx * self.vx + self.vy * self.vy).sqrt();
+        if mag > 1e-6 {
+            let target = if self.effects.speed > 0.0 { self.base_speed * SPEED_BURST } else { self.base_speed };
+            let scale = target / mag;
+            self.vx *= scale;
+            self.vy *= scale;
+        }
+    }
+}
+
+const TEAM_BOOST: f64 = 1.12;
+pub(crate) const MAX_BASE_SPEED: f64 = 520.0;
+const MIN_BASE_SPEED: f64 = 60.0; // floor for damped walls and added balls
+const BOUNCE_COOLDOWN: f64 = 0.015; // seconds between territory bounces
+
+/// Mirror `b` off a wall with inward normal `n`, then apply the wall's own effect.
+fn hit_wall(b: &mut Ball, kind: WallKind, (nx, ny): (f64, f64), rng: &mut Rng) {
+    let dot = b.vx * nx + b.vy * ny;
+    b.vx -= 2.0 * dot * nx;
+    b.vy -= 2.0 * dot * ny;
+    match kind {
+        WallKind::Elastic | WallKind::Owned(_) => {}
+        WallKind::Damped(keep) => {
+            b.base_speed = (b.base_speed * keep).max(MIN_BASE_SPEED);
+            b.maintain_speed();
+        }
+        WallKind::Scatter(spread) => {
+            let normal = ny.atan2(nx);
+            let turn = b.vy.atan2(b.vx) - normal + rng.range(-spread, spread);
+            let turn = (turn + PI).rem_euclid(2.0 * PI) - PI; // relative to the normal, in -PI..PI
+            let ang = normal + turn.clamp(-0.45 * PI, 0.45 * PI);
+            let speed = b.vx.hypot(b.vy);
+            (b.vx, b.vy) = (ang.cos() * speed, ang.sin() * speed);
+        }
+        WallKind::Sticky(secs) => b.stuck = secs,
+    }
+}
+
+const RESTITUTION: f64 = 0.98; // slightly inelastic ball-ball bounces, for liveliness
+
+/// Separate two overlapping balls (the lighter moving further) and exchange the
+/// mass-weighted impulse along the unit normal `n` from `bi` to `bj`; both steps conserve
+/// momentum. A shielded ball can't be pushed: it is treated as immovable unless both are.
+/// Returns the impulse, or None if the balls are already moving apart.
+fn bounce_pair(bi: &mut Ball, bj: &mut Ball, (nx, ny): (f64, f64), penetration: f64) -> Option<f64> {
+    let (inv_i, inv_j) = match (bi.shielded(), bj.shielded()) {
+        (true, false) => (0.0, 1.0 / bj.mass),
+        (false, true) => (1.0 / bi.mass, 0.0),
+        _ => (1.0 / bi.mass, 1.0 / bj.mass),
+    };
+    let inv_sum = inv_i + inv_j;
+
+    let corr = (penetration + 2e-4) / inv_sum;
+    bi.x -= nx * corr * inv_i; bi.y -= ny * corr * inv_i;
+    bj.x += nx * corr * inv_j; bj.y += ny * corr * inv_j;
+
+    // Relative velocity along the normal; j = -(1+e)*vn / (1/m1+1/m2)
+    let vn = (bj.vx - bi.vx) * nx + (bj.vy - bi.vy) * ny;
+    if vn >= 0.0 { return None; }
+    let j = -(1.0 + RESTITUTION) * vn / inv_sum;
+    bi.vx -= j * nx * inv_i; bi.vy -= j * ny * inv_i;
+    bj.vx += j * nx * inv_j; bj.vy += j * ny * inv_j;
+    Some(j)
+}
+
+/// Small seeded PRNG (SplitMix64) so headless runs are reproducible.
+#[derive(Clone)]
+pub(crate) struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng { Rng(seed) }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in [0, 1).
+    pub fn next_f64(&mut self) -> f64 { (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64 }
+
+    pub fn range(&mut self, min: f64, max: f64) -> f64 { min + (max - min) * self.next_f64() }
+
+    /// Uniform index in `0..len`; `len` must be non-zero.
+    pub fn index(&mut self, len: usize) -> usize { (self.next_u64() % len as u64) as usize }
+}
+
+/// Corners of the flat-top hex of radius `r` centred at `(cx,cy)`, starting at 0 degrees.
+pub(crate) fn hex_corners(cx: f64, cy: f64, r: f64) -> [(f64, f64); 6] {
+    let mut pts = [(0.0, 0.0); 6];
+    for (i, p) in pts.iter_mut().enumerate() {
+        let ang = (i as f64) * 60.0 * PI / 180.0;
+        *p = (cx + r * ang.cos(), cy + r * ang.sin());
+    }
+    pts
+}
+
+/// Hex radius used for a world of the given size.
+pub(crate) fn hex_radius_for(width: f64, height: f64) -> f64 {
+    (width.min(height) / 50.0).clamp(3.0, 14.0)
+}
+
+/// `p` kept where a ball of radius `r` touches neither end of `0..size`; the middle if the
+/// ball is too big for that (a world shrunk under it).
+fn inside(p: f64, r: f64, size: f64) -> f64 {
+    if 2.0 * r < size { p.clamp(r, size - r) } else { size * 0.5 }
+}
+
+/// Shortest displacement `(dx, dy)` when the world wraps with `period`; unchanged otherwise.
+pub(crate) fn wrap_delta(dx: f64, dy: f64, period: Option<(f64, f64)>) -> (f64, f64) {
+    match period {
+        Some((w, h)) => (dx - w * (dx / w).round(), dy - h * (dy / h).round()),
+        None => (dx, dy),
+    }
+}
+
+const MIN_HEX_RADIUS: f64 = 1.5;
+const MAX_CELLS: f64 = 250_000.0;
+
+/// How the hex size is picked when the grid is built.
+#[derive(Clone, Copy)]
+pub(crate) enum GridResolution {
+    /// `hex_radius_for` the world size.
+    Auto,
+    Radius(f64),
+    /// Largest hexes giving at least `cols` columns and `rows` rows; 0 leaves that axis free.
+    Cells { cols: usize, rows: usize },
+}
+
+impl GridResolution {
+    pub fn radius(self, width: f64, height: f64) -> f64 {
+        match self {
+            GridResolution::Auto => hex_radius_for(width, height),
+            GridResolution::Radius(r) => r,
+            GridResolution::Cells { cols, rows } => {
+                // Inverse of the counting loops in `Grid::new`, nudged down against rounding
+                let by_cols = if cols > 0 { (width - 1.0) / (1.5 * cols as f64 + 0.5) } else { f64::INFINITY };
+                let by_rows = if rows > 0 { (height - 1.0) / (3f64.sqrt() * (rows as f64 + 0.5)) } else { f64::INFINITY };
+                by_cols.min(by_rows) * (1.0 - 1e-9)
+            }
+        }
+    }
+
+    /// `radius`, or why it can't be used: no count given, hexes under 1.5 units, or more
+    /// than 250k cells.
+    pub fn checked_radius(self, width: f64, height: f64) -> Result<f64, String> {
+        if let GridResolution::Cells { cols: 0, rows: 0 } = self {
+            return Err("need a column or row count".to_string());
+        }
+        let r = self.radius(width, height);
+        if !r.is_finite() || r < MIN_HEX_RADIUS {
+            return Err(format!("hex radius must be at least {} (got {:.2})", MIN_HEX_RADIUS, r));
+        }
+        let cells = (width / (1.5 * r)) * (height / (3f64.sqrt() * r));
+        if cells > MAX_CELLS {
+            return Err(format!("grid would have about {:.0} cells; the limit is {}", cells, MAX_CELLS));
+        }
+        Ok(r)
+    }
+}
+
+/// The arena: grid, balls, pickups and scoring, with no browser dependencies.
+pub(crate) struct Sim {
+    pub width: f64, pub height: f64,
+    pub wrap: bool, // toroidal: balls leaving one edge come back on the opposite one
+    pub walls: [WallKind; 4], // by `EDGE_NAMES`; ignored while `wrap` is on
+
+    pub grid: Grid,
+    pub resolution: GridResolution,
+    pub balls: Vec<Ball>,
+    pub next_ball_id: u32,
+
+    pub balls_per_team: u32,
+    pub spawn_mix: [SpawnMix; 2],
+    pub handicaps: [Handicap; 2],
+    pub spawn_layout: SpawnLayout,
+    pub spawn_zones: [Vec<(f64, f64, f64, f64)>; 2], // (x, y, w, h) per team, for `SpawnLayout::Zones`
+
+    pub powerups: Vec<PowerUp>,
+    pub powerup_cfg: PowerUpConfig,
+    pub powerup_timer: f64,
+
+    pub fields: Vec<ForceField>,
+
+    pub players: [PlayerSteer; 2],
+    pub controllers: [Option<Box<dyn BallController>>; 2],
+
+    // Points (flip-based scoring)
+    pub points_white: usize,
+    pub points_black: usize,
+    pub team_stats: [TeamStats; 2], // by `Team::index`
+
+    pub clock: f64, // simulated seconds since the sim was created
+    pub rng: Rng,
+
+    pub match_start: f64, // clock at the last grid reset
+    pub series: ShareSeries,
+
+    pub events: Vec<SimEvent>, // from the latest `step` only
+}
+
+impl Grid {
+    pub fn new(css_w: f64, css_h: f64, r: f64) -> Grid {
+        let hex_h = (3.0f64).sqrt() * r;
+        let step_x = 1.5 * r;
+
+        // columns
+        let mut cols = 0usize; let mut x = r;
+        while x + r <= css_w - 1.0 { cols += 1; x += step_x; }
+        if cols == 0 { cols = 1; }
+
+        // rows (min of even/odd columns)
+        let mut rows_even = 0usize; let mut y_even = hex_h / 2.0;
+        while y_even + hex_h / 2.0 <= css_h - 1.0 { rows_even += 1; y_even += hex_h; }
+        let mut rows_odd = 0usize; let mut y_odd = hex_h;
+        while y_odd + hex_h / 2.0 <= css_h - 1.0 { rows_odd += 1; y_odd += hex_h; }
+        let rows = rows_even.min(rows_odd).max(1);
+
+        let mut cells = Vec::with_capacity(cols * rows);
+        let mid_x = css_w * 0.5;
+        for col in 0..cols {
+            let cx = r + (col as f64) * step_x;
+            let offset_y = if col.is_multiple_of(2) { 0.0 } else { hex_h / 2.0 };
+            for row in 0..rows {
+                let cy = hex_h / 2.0 + offset_y + (row as f64) * hex_h;
+                let color = if cx < mid_x { HexColor::White } else { HexColor::Black };
+                cells.push(Cell { cx, cy, color, flips: 0, held: [0.0; 2], since: 0.0 });
+            }
+        }
+        Grid { cells, cols, rows, r, period: None }
+    }
+
+    /// Lattice that repeats every `w`×`h`, for a wrapped arena: an even number of columns and a
+    /// whole number of rows, so spacing across the seams matches the spacing inside. The
+    /// steps are stretched to fit; hexes are drawn just big enough to cover the gaps.
+    pub fn tiled(w: f64, h: f64, r: f64) -> Grid {
+        let cols = (2.0 * (w / (3.0 * r)).round()).max(2.0) as usize;
+        let rows = (h / (3f64.sqrt() * r)).round().max(1.0) as usize;
+        let (step_x, hex_h) = (w / cols as f64, h / rows as f64);
+
+        let mut cells = Vec::with_capacity(cols * rows);
+        for col in 0..cols {
+            let cx = (col as f64 + 0.5) * step_x;
+            let offset_y = if col.is_multiple_of(2) { 0.0 } else { hex_h / 2.0 };
+            for row in 0..rows {
+                let cy = hex_h / 2.0 + offset_y + (row as f64) * hex_h;
+                let color = if cx < w * 0.5 { HexColor::White } else { HexColor::Black };
+                cells.push(Cell { cx, cy, color, flips: 0, held: [0.0; 2], since: 0.0 });
+            }
+        }
+        let r = (step_x / 1.5).max(hex_h / 3f64.sqrt());
+        Grid { cells, cols, rows, r, period: Some((w, h)) }
+    }
+
+    /// Claim every hex within `radius` of `(x,y)`; returns awarded points and bounce normal.
+    fn flip_disc(&mut self, x: f64, y: f64, radius: f64, team: Team, now: f64, events: &mut Vec<SimEvent>) -> Claim {
+        let target = match team { Team::Black => HexColor::Black, Team::White => HexColor::White };
+        let mut white_pts = 0usize;
+        let mut black_pts = 0usize;
+        let mut stolen = 0usize;
+        let r2 = radius * radius;
+        let mut nx = 0.0;
+        let mut ny = 0.0;
+        let mut hits = 0usize;
+
+        for cell in &mut self.cells {
+            let (dx, dy) = wrap_delta(cell.cx - x, cell.cy - y, self.period);
+            if dx * dx + dy * dy > r2 { continue; }
+            if cell.color == target { continue; }
+
+            let old = cell.color;
+            events.push(SimEvent::Capture { x: cell.cx, y: cell.cy, from: cell.owner() });
+            cell.held[cell.owner().index()] += now - cell.since;
+            cell.since = now;
+            cell.color = target;
+            if cell.flips > 0 { stolen += 1; }
+            cell.flips += 1;
+            match (old, target) {
+                (HexColor::Black, HexColor::White) => white_pts += 1,
+                (HexColor::White, HexColor::Black) => black_pts += 1,
+                _ => {}
+            }
+
+            let (vx, vy) = (-dx, -dy);
+            let len = (vx * vx + vy * vy).sqrt();
+            if len > 1e-6 {
+                nx += vx / len;
+                ny += vy / len;
+                hits += 1;
+            }
+        }
+
+        let normal = if hits > 0 {
+            let len = (nx * nx + ny * ny).sqrt();
+            if len > 1e-6 { Some((nx / len, ny / len)) } else { None }
+        } else {
+            None
+        };
+
+        Claim { white: white_pts, black: black_pts, stolen, normal }
+    }
+
+    /// Hexes owned by (white, black).
+    pub fn territory(&self) -> (usize, usize) {
+        let white = self.cells.iter().filter(|c| c.color == HexColor::White).count();
+        (white, self.cells.len() - white)
+    }
+}
+
+impl Sim {
+    pub fn new(width: f64, height: f64, seed: u64) -> Sim {
+        Sim {
+            width, height, wrap: false, walls: [WallKind::Elastic; 4],
+            grid: Grid::new(width, height, hex_radius_for(width, height)), // clock starts at 0
+            resolution: GridResolution::Auto,
+            balls: vec![], next_ball_id: 0,
+            balls_per_team: 0, spawn_mix: [SpawnMix::default(); 2], handicaps: [Handicap::default(); 2],
+            spawn_layout: SpawnLayout::Sides, spawn_zones: [vec![], vec![]],
+            powerups: vec![], powerup_cfg: PowerUpConfig::default(), powerup_timer: 0.0,
+            fields: vec![],
+            players: [PlayerSteer::default(); 2],
+            controllers: [None, None],
+            points_white: 0, points_black: 0, team_stats: [TeamStats::default(); 2],
+            clock: 0.0,
+            rng: Rng::new(seed),
+            match_start: 0.0,
+            series: ShareSeries::default(),
+            events: vec![],
+        }
+    }
+
+    /// Fresh starting grid for the current world size, with hold times starting now.
+    fn build_grid(&self) -> Grid {
+        let r = self.resolution.radius(self.width, self.height);
+        let mut grid = if self.wrap { Grid::tiled(self.width, self.height, r) } else { Grid::new(self.width, self.height, r) };
+        for c in &mut grid.cells { c.since = self.clock; }
+        grid.period = self.period();
+        grid
+    }
+
+    fn period(&self) -> Option<(f64, f64)> { self.wrap.then_some((self.width, self.height)) }
+
+    /// Join opposite edges (or restore the walls, pulling balls back inside). The two
+    /// modes lay the hexes out differently, so this starts a fresh board.
+    pub fn set_wrap(&mut self, wrap: bool) {
+        self.wrap = wrap;
+        if !wrap {
+            for b in &mut self.balls {
+                let r = b.size();
+                b.x = inside(b.x, r, self.width);
+                b.y = inside(b.y, r, self.height);
+            }
+        }
+        self.reset_grid();
+    }
+
+    fn wrap_positions(&mut self) {
+        if !self.wrap { return; }
+        for b in &mut self.balls {
+            b.x = b.x.rem_euclid(self.width);
+            b.y = b.y.rem_euclid(self.height);
+        }
+    }
+
+    /// New world size; a chosen hex size that would break the `set_resolution` limits at
+    /// this size falls back to `GridResolution::Auto`.
+    pub fn resize(&mut self, width: f64, height: f64) {
+        self.width = width; self.height = height;
+        if self.resolution.checked_radius(width, height).is_err() { self.resolution = GridResolution::Auto; }
+        self.grid = self.build_grid();
+
+        for b in &mut self.balls {
+            let r = b.size();
+            b.x = inside(b.x, r, self.width);
+            b.y = inside(b.y, r, self.height);
+        }
+        self.powerups.clear();
+    }
+
+    /// Pick the hex size; rebuilds the grid and respawns balls sized to it. Fails, leaving
+    /// the sim untouched, if the hexes would be under 1.5 units or fill more than 250k cells.
+    pub fn set_resolution(&mut self, resolution: GridResolution) -> Result<(), String> {
+        resolution.checked_radius(self.width, self.height)?;
+        self.resolution = resolution;
+        self.grid = self.build_grid();
+        self.spawn_balls_per_team(self.balls_per_team);
+        Ok(())
+    }
+
+    pub fn reset_grid(&mut self) {
+        self.grid = self.build_grid();
+        self.points_white = 0;
+        self.points_black = 0;
+        self.powerups.clear();
+        self.powerup_timer = 0.0;
+        self.match_start = self.clock;
+        self.series = ShareSeries::default();
+        self.team_stats = [TeamStats::default(); 2];
+        for b in &mut self.balls { b.stats = BallStats::default(); }
+    }
+
+    /// Choose how many of `team`'s balls spawn heavy or light; respawns balls.
+    pub fn set_ball_mix(&mut self, team: Team, heavy: u32, light: u32) {
+        self.spawn_mix[team.index()] = SpawnMix { heavy, light };
+        self.spawn_balls_per_team(self.balls_per_team);
+    }
+
+    pub fn spawn_balls_per_team(&mut self, per_team: u32) {
+        self.balls.clear();
+        self.balls_per_team = per_team;
+
+        let (r, speed) = self.standard_ball();
+
+        let mirror = matches!(self.spawn_layout, SpawnLayout::Mirrored | SpawnLayout::Formation(_));
+        let mut white_spots = Vec::with_capacity(per_team as usize);
+        for team in [Team::White, Team::Black] {
+            let mix = self.spawn_mix[team.index()];
+            let hc = self.handicaps[team.index()];
+            let n = (per_team as i32 + hc.extra_balls).max(0) as u32;
+            for i in 0..n {
+                let (r_mul, mass, speed_mul) = mix.class_for(i).profile();
+                let (r, speed) = ((r * r_mul * hc.radius).min(self.max_ball_radius()), (speed * speed_mul * hc.speed).min(hc.max_speed));
+                let (x, y, ang) = match team {
+                    Team::Black if mirror => {
+                        // Point reflection of the matching white ball through the centre
+                        let (x, y, ang) = match white_spots.get(i as usize) {
+                            Some(&spot) => spot,
+                            None => self.spawn_spot(Team::White, i, n, r),
+                        };
+                        ((self.width - x).clamp(r, self.width - r), (self.height - y).clamp(r, self.height - r), ang + PI)
+                    }
+                    _ => self.spawn_spot(team, i, n, r),
+                };
+                if team == Team::White { white_spots.push((x, y, ang)); }
+                self.balls.push(Ball {
+                    id: self.next_ball_id,
+                    x, y,
+                    vx: ang.cos() * speed,
+                    vy: ang.sin() * speed,
+                    team,
+                    radius: r,
+                    mass,
+                    base_speed: speed,
+                    max_speed: hc.max_speed,
+                    last_bounce_ts: -1.0,
+                    effects: Effects::default(),
+                    player: false,
+                    stats: BallStats::default(),
+                    stuck: 0.0,
+                });
+                self.next_ball_id += 1;
+            }
+        }
+        self.assign_players();
+    }
+
+    /// Largest ball radius that fits between opposite walls with room to spare, even grown.
+    pub fn max_ball_radius(&self) -> f64 {
+        (self.width.min(self.height) * 0.5 - 2.0).max(1.0) / GROW_FACTOR
+    }
+
+    /// Radius and speed of a standard ball on the current grid.
+    fn standard_ball(&self) -> (f64, f64) {
+        ((self.grid.r * 1.8).clamp(6.0, 22.0), (self.grid.r * 20.0).clamp(200.0, 480.0))
+    }
+
+    /// Drop one ball into the running match and return its id; other balls and the grid are
+    /// untouched. A zero velocity picks a random heading at the standard speed, and a zero
+    /// radius the standard size. Mass scales with area; the radius is capped by
+    /// `max_ball_radius` and the speed kept between the damped-wall floor and the team's cap.
+    pub fn add_ball(&mut self, team: Team, (x, y): (f64, f64), (mut vx, mut vy): (f64, f64), radius: f64) -> u32 {
+        let (std_r, std_speed) = self.standard_ball();
+        let max_speed = self.handicaps[team.index()].max_speed;
+        let r = if radius > 0.0 { radius } else { std_r }.min(self.max_ball_radius());
+        if vx.hypot(vy) < 1e-6 {
+            let ang = self.rng.range(0.0, 2.0 * PI);
+            (vx, vy) = (ang.cos() * std_speed, ang.sin() * std_speed);
+        }
+        let id = self.next_ball_id;
+        self.next_ball_id += 1;
+        let mut b = Ball {
+            id,
+            x: inside(x, r, self.width),
+            y: inside(y, r, self.height),
+            vx, vy,
+            team,
+            radius: r,
+            mass: (r / std_r).powi(2),
+            base_speed: vx.hypot(vy).max(MIN_BASE_SPEED).min(max_speed),
+            max_speed,
+            last_bounce_ts: -1.0,
+            effects: Effects::default(),
+            player: false,
+            stats: BallStats::default(),
+            stuck: 0.0,
+        };
+        b.maintain_speed();
+        self.balls.push(b);
+        self.assign_players();
+        id
+    }
+
+    /// Take the ball with `id` out of the match; false if there is none. A removed player
+    /// ball hands control to the team's next ball.
+    pub fn remove_ball(&mut self, id: u32) -> bool {
+        let Some(i) = self.balls.iter().position(|b| b.id == id) else { return false };
+        self.balls.remove(i);
+        self.assign_players();
+        true
+    }
+
+    /// Id of the topmost ball covering world point `(x, y)`, counting its images across
+    /// the seams when the arena wraps.
+    pub fn ball_at(&self, x: f64, y: f64) -> Option<u32> {
+        let period = self.period();
+        self.balls.iter().rev().find(|b| {
+            let (dx, dy) = wrap_delta(b.x - x, b.y - y, period);
+            dx.hypot(dy) <= b.size()
+        }).map(|b| b.id)
+    }
+
+    /// Position and heading for `team`'s ball `i` of `n` (radius `r`) under the spawn
+    /// layout; mirrored layouts only ask for white.
+    fn spawn_spot(&mut self, team: Team, i: u32, n: u32, r: f64) -> (f64, f64, f64) {
+        let (w, h) = (self.width, self.height);
+        let facing = match team { Team::White => 0.0, Team::Black => PI };
+        let layout = match self.spawn_layout {
+            SpawnLayout::Zones if self.spawn_zones[team.index()].is_empty() => SpawnLayout::Sides,
+            layout => layout,
+        };
+        match layout {
+            // White in the left quarter heading right-ish, black in the right quarter heading left-ish
+            SpawnLayout::Sides | SpawnLayout::Mirrored => {
+                let x = match team {
+                    Team::White => self.rng.range(r + 1.0, (w * 0.25).max(r + 1.0)),
+                    Team::Black => self.rng.range((w * 0.75).min(w - r - 1.0), w - r - 1.0),
+                };
+                let y = self.rng.range(r + 1.0, h - r - 1.0);
+                (x, y, facing + self.rng.range(-0.35 * PI, 0.35 * PI))
+            }
+            SpawnLayout::Formation(Formation::Line) => {
+                let y = h * (i + 1) as f64 / (n + 1) as f64;
+                ((w * 0.12).max(r + 1.0), y, facing)
+            }
+            SpawnLayout::Formation(Formation::Wedge) => {
+                // Arrowhead pointing at the enemy: the tip, then pairs fanning back above and below
+                let rank = i.div_ceil(2) as f64;
+                let side = if i % 2 == 1 { -1.0 } else { 1.0 };
+                let gap = r * 3.0;
+                let x = (w * 0.25 - rank * gap).max(r + 1.0);
+                let y = (h * 0.5 + side * rank * gap).clamp(r + 1.0, h - r - 1.0);
+                (x, y, facing + side * rank * 0.1)
+            }
+            SpawnLayout::Zones => {
+                let zones = &self.spawn_zones[team.index()];
+                let (zx, zy, zw, zh) = zones[self.rng.index(zones.len())];
+                let x = self.rng.range(zx, zx + zw).clamp(r + 1.0, w - r - 1.0);
+                let y = self.rng.range(zy, zy + zh).clamp(r + 1.0, h - r - 1.0);
+                (x, y, self.rng.range(0.0, 2.0 * PI))
+            }
+            SpawnLayout::Random => {
+                let x = self.rng.range(r + 1.0, w - r - 1.0);
+                let y = self.rng.range(r + 1.0, h - r - 1.0);
+                (x, y, self.rng.range(0.0, 2.0 * PI))
+            }
+        }
+    }
+
+    /// Trade everything that belongs to a participant rather than a colour: controllers,
+    /// spawn mixes, handicaps and player control. Takes effect from the next respawn.
+    pub fn swap_sides(&mut self) {
+        self.controllers.swap(0, 1);
+        self.spawn_mix.swap(0, 1);
+        self.handicaps.swap(0, 1);
+        self.players.swap(0, 1);
+    }
+
+    /// Hand a team's first ball to the player (or back to the simulation).
+    pub fn set_player_control(&mut self, team: Team, enabled: bool) {
+        let p = &mut self.players[team.index()];
+        *p = PlayerSteer { enabled, ..PlayerSteer::default() };
+        self.assign_players();
+    }
+
+    /// Flag the first ball of each player team; keeps a current flag if that ball still exists.
+    fn assign_players(&mut self) {
+        for team in [Team::White, Team::Black] {
+            let enabled = self.players[team.index()].enabled;
+            let has_player = self.balls.iter().any(|b| b.team == team && b.player);
+            for b in self.balls.iter_mut().filter(|b| b.team == team) {
+                if !enabled { b.player = false; }
+            }
+            if enabled && !has_player {
+                if let Some(b) = self.balls.iter_mut().find(|b| b.team == team) { b.player = true; }
+            }
+        }
+    }
+
+    /// Turn balls toward player input or their team controller's output;
+    /// acceleration is capped at `STEER_ACCEL`.
+    fn apply_steering(&mut self, dt: f64) {
+        let view = ArenaView { grid: &self.grid, balls: &self.balls, width: self.width, height: self.height, period: self.period() };
+        let mut accel = Vec::with_capacity(self.balls.len());
+        for (i, b) in self.balls.iter().enumerate() {
+            let a = if b.player {
+                let steer = self.players[b.team.index()];
+                match steer.target {
+                    Some((tx, ty)) => wrap_delta(tx - b.x, ty - b.y, view.period),
+                    None => steer.dir,
+                }
+            } else if let Some(ctrl) = self.controllers[b.team.index()].as_mut() {
+                ctrl.steer(&view, i, &mut self.rng)
+            } else {
+                (0.0, 0.0)
+            };
+            accel.push(a);
+        }
+
+        for (b, (mut ax, mut ay)) in self.balls.iter_mut().zip(accel) {
+            let len = (ax * ax + ay * ay).sqrt();
+            if !len.is_finite() || len < 1e-6 || b.stuck > 0.0 { continue; }
+            if len > 1.0 { ax /= len; ay /= len; }
+            b.vx += ax * STEER_ACCEL * dt;
+            b.vy += ay * STEER_ACCEL * dt;
+            b.maintain_speed();
+        }
+    }
+
+    /// Bend balls along the force fields; like steering, this turns them without changing
+    /// their cruising speed.
+    fn apply_fields(&mut self, dt: f64) {
+        if self.fields.is_empty() { return; }
+        let period = self.period();
+        for b in &mut self.balls {
+            if b.stuck > 0.0 { continue; }
+            let (mut ax, mut ay) = (0.0, 0.0);
+            for f in &self.fields {
+                let (fx, fy) = f.accel_at(b.x, b.y, period);
+                ax += fx;
+                ay += fy;
+            }
+            b.vx += ax * dt;
+            b.vy += ay * dt;
+            b.maintain_speed();
+        }
+    }
+
+    /// Advance the simulation by `dt` simulated seconds; returns true if points changed.
+    pub fn step(&mut self, dt: f64) -> bool {
+        self.clock += dt;
+        self.events.clear();
+        let (w, h) = (self.width, self.height);
+
+        // --- Phase 0: player & AI steering ---
+        self.apply_steering(dt);
+        self.apply_fields(dt);
+
+        // --- Phase 1: integrate + wall bounces ---
+        for b in &mut self.balls {
+            let had_speed = b.effects.speed > 0.0;
+            b.effects.speed = (b.effects.speed - dt).max(0.0);
+            b.effects.grow = (b.effects.grow - dt).max(0.0);
+            b.effects.shield = (b.effects.shield - dt).max(0.0);
+            if had_speed && b.effects.speed == 0.0 { b.maintain_speed(); }
+
+            let speed = b.vx.hypot(b.vy);
+            let pinned = b.stuck > 0.0;
+            if pinned {
+                b.stuck = (b.stuck - dt).max(0.0);
+            } else {
+                b.x += b.vx * dt;
+                b.y += b.vy * dt;
+                b.stats.distance += speed * dt;
+            }
+            b.stats.peak_speed = b.stats.peak_speed.max(speed);
+
+            // A pinned ball already bounced; it leaves along the mirrored heading once released
+            if self.wrap || pinned { continue; }
+            let r = b.size();
+            let (mut nx, mut ny) = (0.0, 0.0);
+            // (inward normal, overlapping, centre already past the edge, shift to the opposite edge)
+            let edges = [
+                ((1.0, 0.0), b.x - r <= 0.0, b.x < 0.0, (w, 0.0)),
+                ((-1.0, 0.0), b.x + r >= w, b.x > w, (-w, 0.0)),
+                ((0.0, 1.0), b.y - r <= 0.0, b.y < 0.0, (0.0, h)),
+                ((0.0, -1.0), b.y + r >= h, b.y > h, (0.0, -h)),
+            ];
+            for (&kind, ((ex, ey), touching, past, (sx, sy))) in self.walls.iter().zip(edges) {
+                if !touching { continue; }
+                if kind == WallKind::Owned(b.team) {
+                    if past { b.x += sx; b.y += sy; }
+                    continue;
+                }
+                // Back inside along the normal, then bounce only if still heading out
+                if ex != 0.0 { b.x = inside(b.x, r, w); } else { b.y = inside(b.y, r, h); }
+                if b.vx * ex + b.vy * ey < 0.0 {
+                    hit_wall(b, kind, (ex, ey), &mut self.rng);
+                    nx += ex;
+                    ny += ey;
+                }
+            }
+            if (nx, ny) != (0.0, 0.0) {
+                b.stats.wall_bounces += 1;
+                self.events.push(SimEvent::WallBounce { x: b.x - nx * r, y: b.y - ny * r, nx, ny, speed });
+            }
+        }
+
+        self.wrap_positions();
+
+        // --- Phase 2: ball-ball collisions ---
+        self.resolve_collisions();
+        self.wrap_positions();
+
+        // --- Phase 3: claim & scoring (flip-based) ---
+        let mut points_changed = false;
+        for i in 0..self.balls.len() {
+            let (x, y, radius, team, last_bounce_ts) = {
+                let b = self.balls[i];
+                (b.x, b.y, b.size() * self.handicaps[b.team.index()].claim, b.team, b.last_bounce_ts)
+            };
+            let claim = self.grid.flip_disc(x, y, radius, team, self.clock, &mut self.events);
+            if claim.white > 0 { self.points_white += claim.white; points_changed = true; }
+            if claim.black > 0 { self.points_black += claim.black; points_changed = true; }
+            self.record_claim(i, &claim);
+            if let Some((nx, ny)) = claim.normal {
+                let now = self.clock;
+                if last_bounce_ts < 0.0 || now - last_bounce_ts > BOUNCE_COOLDOWN {
+                    let b = &mut self.balls[i];
+                    let dot = b.vx * nx + b.vy * ny;
+                    if dot < 0.0 {
+                        b.vx -= 2.0 * dot * nx;
+                        b.vy -= 2.0 * dot * ny;
+                        b.maintain_speed();
+                        b.last_bounce_ts = now;
+                        b.stats.territory_bounces += 1;
+                    }
+                }
+            }
+        }
+        // --- Phase 4: power-up pickups & spawns ---
+        if self.update_powerups(dt) { points_changed = true; }
+
+        if self.series.due(self.clock) {
+            self.series.record(self.clock, self.grid.territory(), (self.points_white, self.points_black));
+        }
+        points_changed
+    }
+
+    /// Add a claim by ball `i` to its own and its team's counters.
+    fn record_claim(&mut self, i: usize, claim: &Claim) {
+        let (flipped, stolen) = ((claim.white + claim.black) as u32, claim.stolen as u32);
+        let b = &mut self.balls[i];
+        b.stats.cells_flipped += flipped;
+        b.stats.cells_stolen += stolen;
+        let team = &mut self.team_stats[b.team.index()];
+        team.cells_flipped += flipped;
+        team.cells_stolen += stolen;
+    }
+
+    /// Advance the spawn timer, spawn pickups, and apply any collected this frame.
+    /// Returns true if a paint bomb awarded points.
+    fn update_powerups(&mut self, dt: f64) -> bool {
+        let cfg = &self.powerup_cfg;
+        if !cfg.enabled { return false; }
+
+        self.powerup_timer += dt;
+        if self.powerup_timer >= cfg.interval && self.powerups.len() < cfg.max_active {
+            self.powerup_timer = 0.0;
+            self.spawn_powerup();
+        }
+
+        // Collect: first ball touching a pickup takes it
+        let mut collected = Vec::new();
+        let period = self.period();
+        self.powerups.retain(|p| {
+            let hit = self.balls.iter().position(|b| {
+                let (dx, dy) = wrap_delta(b.x - p.x, b.y - p.y, period);
+                let reach = b.size() + POWERUP_RADIUS;
+                dx * dx + dy * dy <= reach * reach
+            });
+            match hit {
+                Some(i) => { collected.push((i, p.kind)); false }
+                None => true,
+            }
+        });
+
+        let mut scored = false;
+        let duration = self.powerup_cfg.duration;
+        for (i, kind) in collected {
+            match kind {
+                PowerUpKind::Speed => {
+                    let b = &mut self.balls[i];
+                    b.effects.speed = duration;
+                    b.maintain_speed();
+                }
+                PowerUpKind::Grow => self.balls[i].effects.grow = duration,
+                PowerUpKind::Shield => self.balls[i].effects.shield = duration,
+                PowerUpKind::PaintBomb => {
+                    let b = self.balls[i];
+                    let radius = (self.grid.r * PAINT_BOMB_HEXES).max(b.size() * 3.0);
+                    let claim = self.grid.flip_disc(b.x, b.y, radius, b.team, self.clock, &mut self.events);
+                    self.points_white += claim.white;
+                    self.points_black += claim.black;
+                    self.record_claim(i, &claim);
+                    scored |= claim.white + claim.black > 0;
+                }
+                PowerUpKind::Split => {
+                    // Two halves with the parent's total area and mass, fanned apart
+                    let b = &mut self.balls[i];
+                    if b.radius / 2f64.sqrt() < 3.0 { continue; }
+                    b.radius /= 2f64.sqrt();
+                    b.mass *= 0.5;
+                    let mut twin = *b;
+                    let spread = 0.25 * PI;
+                    let (c, s) = (spread.cos(), spread.sin());
+                    (b.vx, b.vy) = (b.vx * c - b.vy * s, b.vx * s + b.vy * c);
+                    (twin.vx, twin.vy) = (twin.vx * c + twin.vy * s, -twin.vx * s + twin.vy * c);
+                    twin.effects = Effects::default();
+                    twin.player = false;
+                    twin.stats = BallStats::default();
+                    twin.id = self.next_ball_id;
+                    self.next_ball_id += 1;
+                    self.balls.push(twin);
+                }
+            }
+        }
+        scored
+    }
+
+    fn spawn_powerup(&mut self) {
+        let cfg = &self.powerup_cfg;
+        let kinds: Vec<PowerUpKind> = POWERUP_KINDS.iter().copied().filter(|k| cfg.kinds[k.index()]).collect();
+        if kinds.is_empty() { return; }
+        let kind = kinds[self.rng.index(kinds.len())];
+
+        // Map-defined hexes win over random placement; skip ones already holding a pickup
+        let grid = &self.grid;
+        let candidates: Vec<usize> = if cfg.spawn_hexes.is_empty() {
+            (0..grid.cells.len()).collect()
+        } else {
+            cfg.spawn_hexes.iter()
+                .filter(|&&(col, row)| col < grid.cols && row < grid.rows)
+                .map(|&(col, row)| col * grid.rows + row)
+                .collect()
+        };
+        let free: Vec<usize> = candidates.into_iter()
+            .filter(|&i| {
+                let c = &grid.cells[i];
+                self.powerups.iter().all(|p| (p.x - c.cx).hypot(p.y - c.cy) > POWERUP_RADIUS * 2.0)
+            })
+            .collect();
+        if free.is_empty() { return; }
+        let cell = &grid.cells[free[self.rng.index(free.len())]];
+        self.powerups.push(PowerUp { kind, x: cell.cx, y: cell.cy });
+    }
+
+    fn resolve_collisions(&mut self) {
+        let n = self.balls.len();
+        if n < 2 { return; }
+
+        let period = self.period();
+
+        for i in 0..n {
+            for j in (i + 1)..n {
+                // Safe split to borrow two balls mutably
+                let (left, right) = self.balls.split_at_mut(j);
+                let bi = &mut left[i];
+                let bj = &mut right[0];
+
+                let (dx, dy) = wrap_delta(bj.x - bi.x, bj.y - bi.y, period);
+                let rsum = bi.size() + bj.size();
+                let dist2 = dx * dx + dy * dy;
+                if dist2 > rsum * rsum { continue; }
+
+                let mut dist = dist2.sqrt();
+                if dist == 0.0 {
+                    // Rare exact overlap: poke in a random direction
+                    let ang = self.rng.range(0.0, PI * 2.0);
+                    dist = 1e-6;
+                    bi.x -= ang.cos() * 0.001;
+                    bi.y -= ang.sin() * 0.001;
+                }
+                // Collision normal
+                let nx = dx / dist;
+                let ny = dy / dist;
+                let Some(impulse) = bounce_pair(bi, bj, (nx, ny), rsum - dist) else { continue };
+                bi.stats.collisions += 1;
+                bj.stats.collisions += 1;
+                let ri = bi.size();
+                self.events.push(SimEvent::Collision { x: bi.x + nx * ri, y: bi.y + ny * ri, nx, ny, impulse });
+
+                bi.maintain_speed();
+                bj.maintain_speed();
+
+                if bi.team == bj.team {
+                    bi.base_speed = (bi.base_speed * TEAM_BOOST).min(bi.max_speed);
+                    bj.base_speed = (bj.base_speed * TEAM_BOOST).min(bj.max_speed);
+                    bi.maintain_speed();
+                    bj.maintain_speed();
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DT: f64 = 1.0 / 60.0;
+
+    #[test]
+    fn sticky_wall_releases_after_its_hold() {
+        let mut sim = Sim::new(400.0, 300.0, 3);
+        sim.walls = [WallKind::Sticky(0.4); 4];
+        // Wind straight into the left wall, so a re-pin would keep the ball there for good
+        sim.fields.push(ForceField { kind: FieldKind::Wind(PI), x: 0.0, y: 0.0, radius: 0.0, strength: 5000.0 });
+        let id = sim.add_ball(Team::White, (40.0, 150.0), (-300.0, 0.0), 10.0);
+
+        let (mut run, mut longest, mut left_wall) = (0, 0, false);
+        for _ in 0..180 {
+            sim.step(DT);
+            let b = sim.balls.iter().find(|b| b.id == id).unwrap();
+            if b.stuck > 0.0 { run += 1; } else { run = 0; }
+            if longest > 0 && b.x - b.radius > 2.0 { left_wall = true; }
+            longest = longest.max(run);
+        }
+        assert!(longest > 0 && longest as f64 * DT <= 0.4 + DT, "pinned for {} frames", longest);
+        assert!(left_wall);
+    }
+
+    #[test]
+    fn non_finite_steering_is_ignored() {
+        let mut sim = Sim::new(400.0, 300.0, 5);
+        let id = sim.add_ball(Team::White, (100.0, 150.0), (200.0, 0.0), 10.0);
+        sim.set_player_control(Team::White, true);
+        sim.players[0].dir = (f64::NAN, 0.0);
+        for _ in 0..30 { sim.step(DT); }
+        let b = sim.balls.iter().find(|b| b.id == id).unwrap();
+        assert!(b.player && b.x.is_finite() && b.vx.is_finite());
+        assert!(sim.grid.cells.iter().any(|c| c.color == HexColor::Black));
+    }
+
+    #[test]
+    fn pointer_and_picking_cross_the_seam() {
+        let mut sim = Sim::new(400.0, 300.0, 9);
+        sim.set_wrap(true);
+        let id = sim.add_ball(Team::White, (200.0, 150.0), (0.0, 200.0), 12.0);
+        sim.balls[0].x = 395.0;
+        // The straddling ball's image on the left edge is under the pointer too
+        assert_eq!(sim.ball_at(3.0, 150.0), Some(id));
+        assert_eq!(sim.ball_at(200.0, 150.0), None);
+
+        sim.set_player_control(Team::White, true);
+        sim.players[0].target = Some((10.0, 150.0));
+        sim.step(DT);
+        assert!(sim.balls[0].vx > 0.0, "steered the long way round");
+    }
+
+    #[test]
+    fn collision_impulse_conserves_momentum() {
+        let mut sim = Sim::new(400.0, 300.0, 2);
+        let light = sim.add_ball(Team::White, (100.0, 100.0), (250.0, 40.0), 8.0);
+        let heavy = sim.add_ball(Team::Black, (110.0, 108.0), (-120.0, -10.0), 20.0);
+        let (mut a, mut b) = (sim.balls[0], sim.balls[1]);
+        assert!((a.id, b.id) == (light, heavy) && b.mass > 4.0 * a.mass);
+        let momentum = |a: &Ball, b: &Ball| (a.mass * a.vx + b.mass * b.vx, a.mass * a.vy + b.mass * b.vy);
+        let centre = |a: &Ball, b: &Ball| (a.mass * a.x + b.mass * b.x, a.mass * a.y + b.mass * b.y);
+        let (p0, c0) = (momentum(&a, &b), centre(&a, &b));
+
+        let (dx, dy) = (b.x - a.x, b.y - a.y);
+        let (dist, rsum) = (dx.hypot(dy), a.radius + b.radius);
+        let impulse = bounce_pair(&mut a, &mut b, (dx / dist, dy / dist), rsum - dist);
+        assert!(impulse.unwrap() > 0.0);
+        let (p1, c1) = (momentum(&a, &b), centre(&a, &b));
+        assert!((p1.0 - p0.0).abs() < 1e-9 && (p1.1 - p0.1).abs() < 1e-9, "{:?} -> {:?}", p0, p1);
+        assert!((c1.0 - c0.0).abs() < 1e-9 && (c1.1 - c0.1).abs() < 1e-9);
+        // The light ball takes most of the change in velocity
+        assert!((a.vx - 250.0).abs() > (b.vx + 120.0).abs());
+    }
+
+    #[test]
+    fn mirrored_spawns_are_point_reflections() {
+        for layout in [SpawnLayout::Mirrored, SpawnLayout::Formation(Formation::Line), SpawnLayout::Formation(Formation::Wedge)] {
+            let mut sim = Sim::new(400.0, 300.0, 7);
+            sim.spawn_layout = layout;
+            sim.spawn_balls_per_team(5);
+            let (white, black) = sim.balls.split_at(5);
+            assert_eq!(black.len(), 5);
+            for (w, b) in white.iter().zip(black) {
+                assert!(w.team == Team::White && b.team == Team::Black);
+                assert_eq!((b.x, b.y), (400.0 - w.x, 300.0 - w.y));
+                assert!((b.vx + w.vx).abs() < 1e-9 && (b.vy + w.vy).abs() < 1e-9);
+                assert_eq!(b.radius, w.radius);
+            }
+        }
+    }
+
+    #[test]
+    fn adding_and_removing_leaves_the_rest_alone() {
+        let mut sim = Sim::new(400.0, 300.0, 4);
+        sim.spawn_balls_per_team(3);
+        for _ in 0..30 { sim.step(DT); }
+        let state = |sim: &Sim| sim.balls.iter().map(|b| (b.id, b.x, b.y, b.vx, b.vy, b.team.index())).collect::<Vec<_>>();
+        let board = |sim: &Sim| sim.grid.cells.iter().map(|c| c.color).collect::<Vec<_>>();
+        let (before, cells) = (state(&sim), board(&sim));
+
+        let id = sim.add_ball(Team::Black, (200.0, 150.0), (0.0, 0.0), 0.0);
+        assert!(before.iter().all(|b| b.0 < id));
+        assert_eq!(state(&sim)[..6], before[..]);
+        assert!(board(&sim) == cells);
+
+        let gone = before[1].0;
+        assert!(sim.remove_ball(gone));
+        assert!(!sim.remove_ball(gone));
+        let mut expected = before.clone();
+        expected.remove(1);
+        assert_eq!(state(&sim)[..5], expected[..]);
+        assert_eq!(sim.balls.last().unwrap().id, id);
+        assert!(board(&sim) == cells);
+    }
+}
@@ -0,0 +1,136 @@
+use crate::json;
+use crate::sim::{Sim, Team};
+
+/// Running counters for one ball.
+#[derive(Clone, Copy, Default)]
+pub(crate) struct BallStats {
+    pub cells_flipped: u32,
+    pub cells_stolen: u32, // flips of hexes the other team had claimed during the match
+    pub distance: f64,
+    pub collisions: u32,
+    pub wall_bounces: u32,
+    pub territory_bounces: u32,
+    pub peak_speed: f64,
+}
+
+/// Running counters for one team. Kept apart from the balls' own so they still add up
+/// to the team's points after balls are removed or respawned.
+#[derive(Clone, Copy, Default)]
+pub(crate) struct TeamStats {
+    pub cells_flipped: u32,
+    pub cells_stolen: u32,
+}
+
+/// One point of the territory/points time series.
+#[derive(Clone, Copy)]
+pub(crate) struct Sample {
+    pub t: f64,
+    pub white_share: f64,
+    pub black_share: f64,
+    pub points_white: usize,
+    pub points_black: usize,
+}
+
+const SAMPLE_INTERVAL: f64 = 0.5;
+const MAX_SAMPLES: usize = 1024;
+
+/// Territory share sampled at a fixed interval. When full, every other sample is
+/// dropped and the interval doubles, so long matches keep their whole history.
+pub(crate) struct ShareSeries {
+    pub samples: Vec<Sample>,
+    pub interval: f64,
+    next_at: f64,
+}
+
+impl Default for ShareSeries {
+    fn default() -> Self {
+        ShareSeries { samples: vec![], interval: SAMPLE_INTERVAL, next_at: 0.0 }
+    }
+}
+
+impl ShareSeries {
+    /// Whether the interval has elapsed since the last sample.
+    pub fn due(&self, t: f64) -> bool { t >= self.next_at }
+
+    pub fn record(&mut self, t: f64, territory: (usize, usize), points: (usize, usize)) {
+        let total = (territory.0 + territory.1).max(1) as f64;
+        self.samples.push(Sample {
+            t,
+            white_share: territory.0 as f64 / total,
+            black_share: territory.1 as f64 / total,
+            points_white: points.0,
+            points_black: points.1,
+        });
+        if self.samples.len() >= MAX_SAMPLES {
+            let mut i = 0;
+            self.samples.retain(|_| { i += 1; i % 2 == 1 });
+            self.interval *= 2.0;
+        }
+        self.next_at = t + self.interval;
+    }
+}
+
+/// Per-hex activity as row-major matrices (`[row][col]`): flip counts, seconds held by
+/// each team, and which team held the hex longest.
+pub(crate) fn heatmap_json(sim: &Sim) -> String {
+    let grid = &sim.grid;
+    let now = sim.clock;
+    let matrix = |f: &dyn Fn(usize) -> String| {
+        let rows: Vec<String> = (0..grid.rows).map(|row| {
+            let cells: Vec<String> = (0..grid.cols).map(|col| f(col * grid.rows + row)).collect();
+            format!("[{}]", cells.join(","))
+        }).collect();
+        format!("[{}]", rows.join(","))
+    };
+    let held = |i: usize, t: Team| (grid.cells[i].held_until(now)[t.index()] * 10.0).round() / 10.0;
+    format!(
+        "{{\"cols\":{},\"rows\":{},\"flips\":{},\"white_secs\":{},\"black_secs\":{},\"longest_owner\":{}}}",
+        grid.cols, grid.rows,
+        matrix(&|i| grid.cells[i].flips.to_string()),
+        matrix(&|i| json::number(held(i, Team::White))),
+        matrix(&|i| json::number(held(i, Team::Black))),
+        matrix(&|i| {
+            let [w, b] = grid.cells[i].held_until(now);
+            json::string(team_name(if w >= b { Team::White } else { Team::Black }))
+        }),
+    )
+}
+
+fn team_name(team: Team) -> &'static str {
+    match team { Team::White => "white", Team::Black => "black" }
+}
+
+/// Whole-match statistics as a JSON object; see `get_stats` for the layout.
+pub(crate) fn to_json(sim: &Sim) -> String {
+    let (white_cells, black_cells) = sim.grid.territory();
+    let total = (white_cells + black_cells).max(1) as f64;
+
+    let team = |t: Team, points: usize, cells: usize| {
+        let ts = sim.team_stats[t.index()];
+        format!("{{\"points\":{},\"cells\":{},\"territory_share\":{},\"cells_flipped\":{},\"cells_stolen\":{}}}",
+            points, cells, json::number(cells as f64 / total), ts.cells_flipped, ts.cells_stolen)
+    };
+
+    let balls: Vec<String> = sim.balls.iter().map(|b| {
+        let s = &b.stats;
+        format!("{{\"id\":{},\"team\":{},\"cells_flipped\":{},\"cells_stolen\":{},\"distance\":{},\"collisions\":{},\"wall_bounces\":{},\"territory_bounces\":{},\"peak_speed\":{}}}",
+            b.id, json::string(team_name(b.team)), s.cells_flipped, s.cells_stolen, json::number(s.distance.round()),
+            s.collisions, s.wall_bounces, s.territory_bounces, json::number(s.peak_speed.round()))
+    }).collect();
+
+    let series = &sim.series;
+    let col = |f: &dyn Fn(&Sample) -> String| series.samples.iter().map(f).collect::<Vec<_>>().join(",");
+    format!(
+        "{{\"time\":{},\"teams\":{{\"white\":{},\"black\":{}}},\"balls\":[{}],\"series\":{{\"interval\":{},\"t\":[{}],\"white\":[{}],\"black\":[{}],\"points_white\":[{}],\"points_black\":[{}]}}}}",
+        json::number(sim.clock - sim.match_start),
+        team(Team::White, sim.points_white, white_cells),
+        team(Team::Black, sim.points_black, black_cells),
+        balls.join(","),
+        json::number(series.interval),
+        col(&|s| json::number(((s.t - sim.match_start) * 100.0).round() / 100.0)),
+        col(&|s| json::number((s.white_share * 1e4).round() / 1e4)),
+        col(&|s| json::number((s.black_share * 1e4).round() / 1e4)),
+        col(&|s| s.points_white.to_string()),
+        col(&|s| s.points_black.to_string()),
+    )
+}
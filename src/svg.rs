@@ -0,0 +1,71 @@
+use std::fmt::Write;
+
+use crate::sim::{hex_corners, Sim, Team};
+use crate::render::{seam_images, wrap_images};
+use crate::theme::Theme;
+
+const CAPTION_H: f64 = 28.0;
+
+/// Render the board to an SVG document. Hexes and balls match `Grid::draw` / `App::render`;
+/// with `caption` a score line is added below the arena.
+pub(crate) fn export(sim: &Sim, theme: &Theme, caption: bool) -> String {
+    let (w, h) = (sim.width, sim.height);
+    let total_h = if caption { h + CAPTION_H } else { h };
+    let mut out = String::new();
+    let _ = writeln!(out,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{total_h}\" viewBox=\"0 0 {w} {total_h}\">");
+
+    // Focal point sits 0.4r up-left of centre, as in the canvas gradient
+    out.push_str("<defs>\n");
+    for (id, team) in [("ball-white", Team::White), ("ball-black", Team::Black)] {
+        let _ = write!(out, "<radialGradient id=\"{id}\" cx=\"0.5\" cy=\"0.5\" r=\"0.5\" fx=\"0.3\" fy=\"0.3\" fr=\"0.025\">");
+        for (offset, color) in ["0", "0.5", "1"].iter().zip(theme.team(team).ball) {
+            let _ = write!(out, "<stop offset=\"{offset}\" stop-color=\"{}\"/>", color.css());
+        }
+        out.push_str("</radialGradient>\n");
+    }
+    let _ = writeln!(out, "<clipPath id=\"arena\"><rect width=\"{w}\" height=\"{h}\"/></clipPath>");
+    out.push_str("</defs>\n");
+
+    let _ = writeln!(out, "<rect width=\"{w}\" height=\"{total_h}\" fill=\"{}\"/>", theme.background.css());
+
+    // Hexes on a wrapped seam are repeated on the far side, clipped like the balls below
+    let _ = writeln!(out, "<g stroke-width=\"{}\" stroke-linejoin=\"round\" clip-path=\"url(#arena)\">", theme.line_width);
+    for cell in &sim.grid.cells {
+        let owner = theme.owner(cell.color);
+        let (fill, stroke) = (owner.cell.css(), if theme.line_width > 0.0 { owner.line.css() } else { "none".to_string() });
+        for (cx, cy) in seam_images(cell.cx, cell.cy, sim.grid.r, sim.grid.period) {
+            let pts: Vec<String> = hex_corners(cx, cy, sim.grid.r).iter()
+                .map(|(x, y)| format!("{:.2},{:.2}", x, y))
+                .collect();
+            let _ = writeln!(out, "<polygon points=\"{}\" fill=\"{fill}\" stroke=\"{stroke}\"/>", pts.join(" "));
+        }
+    }
+    out.push_str("</g>\n");
+
+    // Balls straddling a wrapped edge are drawn on both sides, clipped to the arena
+    out.push_str("<g clip-path=\"url(#arena)\">\n");
+    for b in &sim.balls {
+        let r = b.size();
+        let grad = match b.team { Team::White => "ball-white", Team::Black => "ball-black" };
+        let stroke = theme.team(b.team).outline.css();
+        for (x, y) in wrap_images(sim, b.x, b.y, r + 1.0) {
+            let _ = writeln!(out,
+                "<circle cx=\"{:.2}\" cy=\"{:.2}\" r=\"{:.2}\" fill=\"url(#{grad})\"/>",
+                x, y, r);
+            // Only the specular dot is outlined, as on the canvas
+            let _ = writeln!(out,
+                "<circle cx=\"{:.2}\" cy=\"{:.2}\" r=\"{:.2}\" fill=\"#ffffff\" fill-opacity=\"0.55\" stroke=\"{stroke}\" stroke-width=\"1\"/>",
+                x - r * 0.45, y - r * 0.45, (r * 0.28).max(0.8));
+        }
+    }
+    out.push_str("</g>\n");
+
+    if caption {
+        let _ = writeln!(out,
+            "<text x=\"{}\" y=\"{:.1}\" fill=\"#e6e6e6\" font-family=\"sans-serif\" font-size=\"14\" font-weight=\"700\" text-anchor=\"middle\">White {} \u{2014} {} Black</text>",
+            w / 2.0, h + CAPTION_H * 0.68, sim.points_white, sim.points_black);
+    }
+    out.push_str("</svg>\n");
+    out
+}
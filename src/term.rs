@@ -0,0 +1,206 @@
+//! Native terminal frontend: the arena drawn with half-blocks (two square dots per
+//! character cell) and balls as coloured glyphs.
+
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::style::{Color, Colors, Print, ResetColor, SetColors};
+use crossterm::{cursor, execute, queue, terminal};
+
+use crate::ai;
+use crate::raster::Framebuffer;
+use crate::render::{seam_images, Paint, Renderer, Rgba};
+use crate::sim::{hex_corners, Sim, SpawnLayout, Team};
+use crate::theme::{Theme, THEME_NAMES};
+
+/// Arena pixels per half-block dot; keeps hex sizes close to the browser's.
+const PX_PER_DOT: f64 = 8.0;
+const FRAME: Duration = Duration::from_millis(33);
+const MAX_SPEED: f64 = 6.25;
+const MIN_SPEED: f64 = 0.25;
+
+const KEYS: &str = "space start/stop  r reset  +/- speed  q quit";
+
+struct Options {
+    balls_per_team: u32,
+    seed: u64,
+    speed: f64,
+    controllers: [Option<String>; 2],
+    theme: Theme,
+    wrap: bool,
+    layout: SpawnLayout,
+}
+
+fn usage() -> String {
+    format!(
+        "usage: hex_war_term [--balls N] [--seed N] [--speed X] [--white AI] [--black AI] [--theme NAME] [--wrap] [--layout L]\n\
+         AI is one of regions, defend, chase, wander; NAME one of {}; L one of {}",
+        THEME_NAMES.join(", "), SpawnLayout::NAMES.join(", "),
+    )
+}
+
+fn parse_args(args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut opts = Options { balls_per_team: 3, seed: 1, speed: 1.0, controllers: [None, None], theme: Theme::classic(), wrap: false, layout: SpawnLayout::Sides };
+    let mut args = args;
+    while let Some(flag) = args.next() {
+        let mut value = || args.next().ok_or_else(|| format!("{} needs a value\n{}", flag, usage()));
+        match flag.as_str() {
+            "--balls" => opts.balls_per_team = value()?.parse().map_err(|_| "bad --balls".to_string())?,
+            "--seed" => opts.seed = value()?.parse().map_err(|_| "bad --seed".to_string())?,
+            "--speed" => opts.speed = value()?.parse::<f64>().map_err(|_| "bad --speed".to_string())?.clamp(MIN_SPEED, MAX_SPEED),
+            "--white" | "--black" => {
+                let name = value()?;
+                if ai::controller_by_name(&name).is_none() { return Err(format!("unknown controller \"{}\"", name)); }
+                let team = if flag == "--white" { Team::White } else { Team::Black };
+                opts.controllers[team.index()] = Some(name);
+            }
+            "--theme" => {
+                let name = value()?;
+                opts.theme = Theme::by_name(&name).ok_or_else(|| format!("unknown theme \"{}\"\n{}", name, usage()))?;
+            }
+            "--wrap" => opts.wrap = true,
+            "--layout" => {
+                let name = value()?;
+                opts.layout = SpawnLayout::parse(&name).ok_or_else(|| format!("unknown layout \"{}\"\n{}", name, usage()))?;
+            }
+            "-h" | "--help" => return Err(usage()),
+            _ => return Err(format!("unknown option \"{}\"\n{}", flag, usage())),
+        }
+    }
+    Ok(opts)
+}
+
+/// Run the terminal frontend until the user quits. `args` excludes the program name.
+pub fn run(args: impl Iterator<Item = String>) -> Result<(), String> {
+    let opts = parse_args(args)?;
+    let (cols, rows) = terminal::size().map_err(|e| e.to_string())?;
+    let (w, h) = arena_size(cols, rows);
+
+    let mut sim = Sim::new(w, h, opts.seed);
+    sim.set_wrap(opts.wrap);
+    sim.spawn_layout = opts.layout;
+    for (i, name) in opts.controllers.iter().enumerate() {
+        sim.controllers[i] = name.as_deref().and_then(ai::controller_by_name);
+    }
+    sim.spawn_balls_per_team(opts.balls_per_team);
+
+    let mut out = io::stdout();
+    terminal::enable_raw_mode().map_err(|e| e.to_string())?;
+    let _ = execute!(out, terminal::EnterAlternateScreen, cursor::Hide);
+    let res = event_loop(&mut out, &mut sim, &opts.theme, opts.speed);
+    let _ = execute!(out, ResetColor, cursor::Show, terminal::LeaveAlternateScreen);
+    let _ = terminal::disable_raw_mode();
+    res.map_err(|e| e.to_string())
+}
+
+/// Arena size for a terminal; the bottom row is kept for the status line.
+fn arena_size(cols: u16, rows: u16) -> (f64, f64) {
+    let dots_y = rows.saturating_sub(1).max(1) as f64 * 2.0;
+    (cols.max(1) as f64 * PX_PER_DOT, dots_y * PX_PER_DOT)
+}
+
+fn event_loop(out: &mut impl Write, sim: &mut Sim, theme: &Theme, speed: f64) -> io::Result<()> {
+    let (mut running, mut speed) = (true, speed);
+    let mut last = Instant::now();
+    loop {
+        while event::poll(Duration::ZERO)? {
+            match event::read()? {
+                Event::Key(k) if k.kind == KeyEventKind::Press => match k.code {
+                    KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                    KeyCode::Char('c') if k.modifiers.contains(KeyModifiers::CONTROL) => return Ok(()),
+                    KeyCode::Char(' ') => running = !running,
+                    KeyCode::Char('r') => sim.reset_grid(),
+                    KeyCode::Char('+') | KeyCode::Char('=') => speed = (speed * 1.25).min(MAX_SPEED),
+                    KeyCode::Char('-') => speed = (speed / 1.25).max(MIN_SPEED),
+                    _ => {}
+                },
+                Event::Resize(cols, rows) => {
+                    let (w, h) = arena_size(cols, rows);
+                    sim.resize(w, h);
+                    queue!(out, terminal::Clear(terminal::ClearType::All))?;
+                }
+                _ => {}
+            }
+        }
+
+        // Same step clamp as the browser loop
+        let now = Instant::now();
+        let dt = (now - last).as_secs_f64().min(0.050);
+        last = now;
+        if running { sim.step(dt * speed); }
+
+        draw(out, sim, theme, running, speed)?;
+        let spent = now.elapsed();
+        if spent < FRAME { event::poll(FRAME - spent)?; }
+    }
+}
+
+fn term_color(c: Rgba) -> Color { Color::Rgb { r: c.r, g: c.g, b: c.b } }
+
+/// Team glyph colour; distinct from both territory shades.
+fn ball_color(team: Team) -> Rgba {
+    match team { Team::White => Rgba::hex("#f59e0b"), Team::Black => Rgba::hex("#38bdf8") }
+}
+
+fn draw(out: &mut impl Write, sim: &Sim, theme: &Theme, running: bool, speed: f64) -> io::Result<()> {
+    let cols = (sim.width / PX_PER_DOT).round() as usize;
+    let dots_y = (sim.height / PX_PER_DOT).round() as usize;
+    let rows = dots_y / 2;
+
+    // Hexes are filled without outlines: at one or two dots per hex they would swamp the fill
+    let mut fb = Framebuffer::new(cols, dots_y, 1.0 / PX_PER_DOT);
+    fb.fill_rect(0.0, 0.0, sim.width, sim.height, theme.background);
+    for cell in &sim.grid.cells {
+        let fill = theme.owner(cell.color).cell;
+        for (cx, cy) in seam_images(cell.cx, cell.cy, sim.grid.r, sim.grid.period) {
+            fb.fill_polygon(&hex_corners(cx, cy, sim.grid.r), &Paint::Solid(fill));
+        }
+    }
+
+    // Glyphs replace both dots of their cell; balls win over power-ups
+    let mut glyphs: Vec<Option<(char, Rgba)>> = vec![None; cols * rows];
+    let mut put = |x: f64, y: f64, glyph: char, color: Rgba| {
+        let (col, row) = ((x / PX_PER_DOT) as usize, (y / PX_PER_DOT / 2.0) as usize);
+        if col < cols && row < rows { glyphs[row * cols + col] = Some((glyph, color)); }
+    };
+    for p in &sim.powerups {
+        let (fill, glyph) = p.kind.icon();
+        put(p.x, p.y, glyph.chars().next().unwrap_or('?'), Rgba::hex(fill));
+    }
+    for b in &sim.balls {
+        put(b.x, b.y, if b.player { '◉' } else { '●' }, ball_color(b.team));
+    }
+
+    let dot = |x: usize, y: usize| {
+        let i = (y * cols + x) * 4;
+        Rgba::rgb(fb.pixels[i], fb.pixels[i + 1], fb.pixels[i + 2])
+    };
+    let mut current: Option<(Rgba, Rgba)> = None;
+    for row in 0..rows {
+        queue!(out, cursor::MoveTo(0, row as u16))?;
+        for col in 0..cols {
+            let (top, bottom) = (dot(col, row * 2), dot(col, row * 2 + 1));
+            let (glyph, colors) = match glyphs[row * cols + col] {
+                Some((glyph, color)) => (glyph, (color, top.lerp(bottom, 0.5))),
+                None => ('▀', (top, bottom)),
+            };
+            if current != Some(colors) {
+                queue!(out, SetColors(Colors::new(term_color(colors.0), term_color(colors.1))))?;
+                current = Some(colors);
+            }
+            queue!(out, Print(glyph))?;
+        }
+    }
+
+    let (tw, tb) = sim.grid.territory();
+    let total = (tw + tb).max(1) as f64;
+    let status = format!(
+        " {} {:.2}x  white {} ({:.0}%)  black {} ({:.0}%)  |  {}",
+        if running { "▶" } else { "⏸" }, speed,
+        sim.points_white, tw as f64 / total * 100.0, sim.points_black, tb as f64 / total * 100.0, KEYS,
+    );
+    let status: String = status.chars().chain(std::iter::repeat(' ')).take(cols).collect();
+    queue!(out, cursor::MoveTo(0, rows as u16), ResetColor, Print(status))?;
+    out.flush()
+}
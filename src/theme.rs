@@ -0,0 +1,126 @@
+use crate::render::Rgba;
+use crate::sim::{HexColor, Team};
+
+/// Colours for one team: its hexes, its balls and its chart lines.
+#[derive(Clone)]
+pub(crate) struct TeamPalette {
+    pub cell: Rgba,
+    pub line: Rgba,      // outline of this team's hexes
+    pub ball: [Rgba; 3], // gradient stops at 0, 0.5 and 1, highlight to rim
+    pub outline: Rgba,   // ring around the specular dot
+    pub accent: Rgba,    // chart lines and labels
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TrailKind { Off, Line, Ghosts }
+
+/// Fading trail behind each ball, tinted with the ball's mid gradient stop.
+#[derive(Clone, Copy)]
+pub(crate) struct TrailStyle {
+    pub kind: TrailKind,
+    pub length: usize, // frames of history, up to `trails::MAX_LEN`
+    pub opacity: f64,  // at the ball end
+}
+
+#[derive(Clone)]
+pub(crate) struct Theme {
+    pub background: Rgba,
+    pub line_width: f64, // hex outlines; 0 hides them
+    pub teams: [TeamPalette; 2], // by `Team::index`
+    pub trail: TrailStyle,
+}
+
+pub(crate) const THEME_NAMES: [&str; 3] = ["classic", "colorblind", "high_contrast"];
+
+impl Theme {
+    pub fn team(&self, team: Team) -> &TeamPalette { &self.teams[team.index()] }
+
+    /// Palette of the team owning a hex of this colour.
+    pub fn owner(&self, color: HexColor) -> &TeamPalette {
+        self.team(match color { HexColor::White => Team::White, HexColor::Black => Team::Black })
+    }
+
+    pub fn by_name(name: &str) -> Option<Theme> {
+        match name.to_ascii_lowercase().replace('-', "_").as_str() {
+            "classic" => Some(Theme::classic()),
+            "colorblind" | "colourblind" | "color_blind" | "colour_blind" => Some(Theme::colorblind()),
+            "high_contrast" | "contrast" => Some(Theme::high_contrast()),
+            _ => None,
+        }
+    }
+
+    /// The original black-and-white board.
+    pub fn classic() -> Theme {
+        Theme {
+            background: Rgba::hex("#111"),
+            line_width: 1.0,
+            trail: TrailStyle { kind: TrailKind::Line, length: 16, opacity: 0.35 },
+            teams: [
+                TeamPalette {
+                    cell: Rgba::hex("#fff"),
+                    line: Rgba::hex("#000"),
+                    ball: [Rgba::hex("#ffffff"), Rgba::hex("#e9e9e9"), Rgba::hex("#cfcfcf")],
+                    outline: Rgba::hex("#000"),
+                    accent: Rgba::hex("#ffffff"),
+                },
+                TeamPalette {
+                    cell: Rgba::hex("#000"),
+                    line: Rgba::hex("#fff"),
+                    ball: [Rgba::hex("#6b6b6b"), Rgba::hex("#181818"), Rgba::hex("#000000")],
+                    outline: Rgba::hex("#fff"),
+                    accent: Rgba::hex("#000000"),
+                },
+            ],
+        }
+    }
+
+    /// Okabe–Ito orange against blue, which stay apart under the common colour-vision deficiencies.
+    pub fn colorblind() -> Theme {
+        Theme {
+            background: Rgba::hex("#1b1b1b"),
+            line_width: 1.0,
+            trail: TrailStyle { kind: TrailKind::Line, length: 16, opacity: 0.45 },
+            teams: [
+                TeamPalette {
+                    cell: Rgba::hex("#e69f00"),
+                    line: Rgba::hex("#a86f00"),
+                    ball: [Rgba::hex("#ffe2a8"), Rgba::hex("#f0b53c"), Rgba::hex("#c98a00")],
+                    outline: Rgba::hex("#3d2a00"),
+                    accent: Rgba::hex("#e69f00"),
+                },
+                TeamPalette {
+                    cell: Rgba::hex("#0072b2"),
+                    line: Rgba::hex("#004f7c"),
+                    ball: [Rgba::hex("#9fd3f2"), Rgba::hex("#2b8ccc"), Rgba::hex("#005a8c")],
+                    outline: Rgba::hex("#e6f4ff"),
+                    accent: Rgba::hex("#56b4e9"),
+                },
+            ],
+        }
+    }
+
+    /// Pure black and white hexes separated by heavy mid-grey outlines.
+    pub fn high_contrast() -> Theme {
+        Theme {
+            background: Rgba::hex("#000"),
+            line_width: 2.0,
+            trail: TrailStyle { kind: TrailKind::Ghosts, length: 12, opacity: 0.5 },
+            teams: [
+                TeamPalette {
+                    cell: Rgba::hex("#fff"),
+                    line: Rgba::hex("#808080"),
+                    ball: [Rgba::hex("#ffffff"), Rgba::hex("#ffffff"), Rgba::hex("#b3b3b3")],
+                    outline: Rgba::hex("#000"),
+                    accent: Rgba::hex("#ffffff"),
+                },
+                TeamPalette {
+                    cell: Rgba::hex("#000"),
+                    line: Rgba::hex("#808080"),
+                    ball: [Rgba::hex("#595959"), Rgba::hex("#000000"), Rgba::hex("#000000")],
+                    outline: Rgba::hex("#fff"),
+                    accent: Rgba::hex("#000000"),
+                },
+            ],
+        }
+    }
+}
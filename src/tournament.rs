@@ -0,0 +1,305 @@
+use crate::ai;
+use crate::json;
+use crate::sim::{Sim, SpawnLayout, SpawnMix, Team};
+
+/// One tournament participant: an AI strategy plus a spawn configuration.
+pub(crate) struct Entrant {
+    pub label: String,
+    pub controller: String,
+    pub mix: SpawnMix,
+}
+
+impl Entrant {
+    /// Parse `"controller"` or `"controller:heavy/light"`, e.g. `"chase:2/0"`.
+    pub fn parse(spec: &str) -> Result<Entrant, String> {
+        let spec = spec.trim();
+        let (controller, mix) = match spec.split_once(':') {
+            Some((name, mix)) => {
+                let (heavy, light) = mix.split_once('/').ok_or_else(|| format!("bad mix in \"{}\"", spec))?;
+                let heavy = heavy.trim().parse().map_err(|_| format!("bad heavy count in \"{}\"", spec))?;
+                let light = light.trim().parse().map_err(|_| format!("bad light count in \"{}\"", spec))?;
+                (name.trim(), SpawnMix { heavy, light })
+            }
+            None => (spec, SpawnMix::default()),
+        };
+        if !controller.eq_ignore_ascii_case("none") && ai::controller_by_name(controller).is_none() {
+            return Err(format!("unknown controller \"{}\"", controller));
+        }
+        Ok(Entrant { label: spec.to_string(), controller: controller.to_string(), mix })
+    }
+}
+
+/// Upper bounds for the page exports, which block the page thread until every match is played.
+pub(crate) const MAX_SEEDS: u32 = 20;
+pub(crate) const MAX_MATCH_SECS: f64 = 300.0;
+pub(crate) const MAX_ENTRANTS: usize = 8;
+pub(crate) const MAX_TOTAL_SECS: f64 = 20_000.0; // simulated seconds over a whole tournament
+
+/// Arena and timing shared by every match.
+pub(crate) struct MatchSettings {
+    pub width: f64,
+    pub height: f64,
+    pub balls_per_team: u32,
+    pub duration: f64, // simulated seconds
+    pub dt: f64,       // fixed step
+    pub layout: SpawnLayout,
+}
+
+impl Default for MatchSettings {
+    fn default() -> Self {
+        MatchSettings { width: 960.0, height: 540.0, balls_per_team: 3, duration: 60.0, dt: 1.0 / 60.0, layout: SpawnLayout::Sides }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub(crate) struct MatchResult {
+    pub points_white: usize,
+    pub points_black: usize,
+}
+
+/// Play one headless match; the same seed always gives the same result.
+pub(crate) fn play_match(settings: &MatchSettings, white: &Entrant, black: &Entrant, seed: u64) -> MatchResult {
+    let mut sim = Sim::new(settings.width, settings.height, seed);
+    sim.spawn_layout = settings.layout;
+    for (team, entrant) in [(Team::White, white), (Team::Black, black)] {
+        sim.spawn_mix[team.index()] = entrant.mix;
+        sim.controllers[team.index()] = ai::controller_by_name(&entrant.controller);
+    }
+    sim.spawn_balls_per_team(settings.balls_per_team);
+
+    let steps = (settings.duration / settings.dt).ceil() as usize;
+    for _ in 0..steps { sim.step(settings.dt); }
+    MatchResult { points_white: sim.points_white, points_black: sim.points_black }
+}
+
+/// Play `seed` twice, `first` taking white in the first leg and black in the second, so
+/// each entrant gets both colours, sides and spawns.
+pub(crate) fn play_fair_pair(settings: &MatchSettings, first: &Entrant, second: &Entrant, seed: u64) -> [MatchResult; 2] {
+    [play_match(settings, first, second, seed), play_match(settings, second, first, seed)]
+}
+
+/// Points of (first, second) over finished legs, where `first` is white in leg one.
+pub(crate) fn fair_totals(legs: &[MatchResult]) -> (usize, usize) {
+    legs.iter().enumerate().fold((0, 0), |(a, b), (i, leg)| {
+        if i % 2 == 0 { (a + leg.points_white, b + leg.points_black) } else { (a + leg.points_black, b + leg.points_white) }
+    })
+}
+
+/// `{"seed", "legs": [{"white", "black"}], "first", "second", "verdict"}`: points per leg by
+/// colour, combined points per entrant, and "first", "second" or "draw" once both legs are
+/// in (`null` before).
+pub(crate) fn fair_json(seed: u64, legs: &[MatchResult]) -> String {
+    format!("{{{}}}", fair_fields(seed, legs))
+}
+
+/// The members of `fair_json` without the braces, for embedding in a larger object.
+pub(crate) fn fair_fields(seed: u64, legs: &[MatchResult]) -> String {
+    let (first, second) = fair_totals(legs);
+    let verdict = if legs.len() < 2 {
+        "null".to_string()
+    } else {
+        json::string(match first.cmp(&second) {
+            std::cmp::Ordering::Greater => "first",
+            std::cmp::Ordering::Equal => "draw",
+            std::cmp::Ordering::Less => "second",
+        })
+    };
+    let legs: Vec<String> = legs.iter()
+        .map(|l| format!("{{\"white\":{},\"black\":{}}}", l.points_white, l.points_black))
+        .collect();
+    format!("\"seed\":{},\"legs\":[{}],\"first\":{},\"second\":{},\"verdict\":{}",
+        json::number(seed as f64), legs.join(","), first, second, verdict)
+}
+
+#[derive(Clone, Copy, Default)]
+pub(crate) struct Record {
+    pub wins: u32,
+    pub draws: u32,
+    pub losses: u32,
+}
+
+impl Record {
+    fn games(&self) -> u32 { self.wins + self.draws + self.losses }
+}
+
+pub(crate) struct Standings {
+    pub labels: Vec<String>,
+    pub ratings: Vec<f64>,
+    pub totals: Vec<Record>,
+    pub matrix: Vec<Vec<Record>>, // matrix[a][b]: a's record against b
+}
+
+const ELO_START: f64 = 1500.0;
+const ELO_K: f64 = 16.0;
+
+/// Round-robin over every pair of entrants and every seed. Each seed is played twice
+/// with sides swapped so neither entrant keeps the better spawn.
+pub(crate) fn run(entrants: &[Entrant], settings: &MatchSettings, seeds: u32, base_seed: u64) -> Standings {
+    let n = entrants.len();
+    let mut ratings = vec![ELO_START; n];
+    let mut totals = vec![Record::default(); n];
+    let mut matrix = vec![vec![Record::default(); n]; n];
+
+    for a in 0..n {
+        for b in (a + 1)..n {
+            for s in 0..seeds as u64 {
+                let seed = base_seed.wrapping_add(s);
+                let legs = play_fair_pair(settings, &entrants[a], &entrants[b], seed);
+                for ((white, black), res) in [(a, b), (b, a)].into_iter().zip(legs) {
+                    let score_white = match res.points_white.cmp(&res.points_black) {
+                        std::cmp::Ordering::Greater => 1.0,
+                        std::cmp::Ordering::Equal => 0.5,
+                        std::cmp::Ordering::Less => 0.0,
+                    };
+
+                    let expected = 1.0 / (1.0 + 10f64.powf((ratings[black] - ratings[white]) / 400.0));
+                    let delta = ELO_K * (score_white - expected);
+                    ratings[white] += delta;
+                    ratings[black] -= delta;
+
+                    for (me, them, score) in [(white, black, score_white), (black, white, 1.0 - score_white)] {
+                        for rec in [&mut totals[me], &mut matrix[me][them]] {
+                            if score == 1.0 { rec.wins += 1; } else if score == 0.5 { rec.draws += 1; } else { rec.losses += 1; }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    Standings { labels: entrants.iter().map(|e| e.label.clone()).collect(), ratings, totals, matrix }
+}
+
+impl Standings {
+    /// Entrant indices, best rating first.
+    fn ranking(&self) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..self.labels.len()).collect();
+        order.sort_by(|&a, &b| self.ratings[b].total_cmp(&self.ratings[a]));
+        order
+    }
+
+    /// Rating table, a blank line, then the win matrix with `W-D-L` cells (row vs column).
+    pub fn to_csv(&self) -> String {
+        let csv_field = |s: &str| if s.contains([',', '"']) { format!("\"{}\"", s.replace('"', "\"\"")) } else { s.to_string() };
+        let mut out = String::from("rank,entrant,rating,games,wins,draws,losses\n");
+        for (rank, &i) in self.ranking().iter().enumerate() {
+            let t = self.totals[i];
+            out.push_str(&format!("{},{},{:.1},{},{},{},{}\n",
+                rank + 1, csv_field(&self.labels[i]), self.ratings[i], t.games(), t.wins, t.draws, t.losses));
+        }
+        out.push('\n');
+        out.push_str("entrant");
+        for label in &self.labels { out.push(','); out.push_str(&csv_field(label)); }
+        out.push('\n');
+        for (i, row) in self.matrix.iter().enumerate() {
+            out.push_str(&csv_field(&self.labels[i]));
+            for (j, rec) in row.iter().enumerate() {
+                if i == j { out.push_str(",-"); } else { out.push_str(&format!(",{}-{}-{}", rec.wins, rec.draws, rec.losses)); }
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    /// `{"ratings":[...], "labels":[...], "matrix":[[{"wins","draws","losses"}]]}`
+    pub fn to_json(&self) -> String {
+        let record = |r: &Record| format!("{{\"wins\":{},\"draws\":{},\"losses\":{}}}", r.wins, r.draws, r.losses);
+        let ratings: Vec<String> = self.ranking().iter().map(|&i| {
+            let t = self.totals[i];
+            format!("{{\"entrant\":{},\"rating\":{},\"games\":{},\"wins\":{},\"draws\":{},\"losses\":{}}}",
+                json::string(&self.labels[i]), json::number((self.ratings[i] * 10.0).round() / 10.0),
+                t.games(), t.wins, t.draws, t.losses)
+        }).collect();
+        let labels: Vec<String> = self.labels.iter().map(|l| json::string(l)).collect();
+        let matrix: Vec<String> = self.matrix.iter()
+            .map(|row| format!("[{}]", row.iter().map(record).collect::<Vec<_>>().join(",")))
+            .collect();
+        format!("{{\"ratings\":[{}],\"labels\":[{}],\"matrix\":[{}]}}", ratings.join(","), labels.join(","), matrix.join(","))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn short() -> MatchSettings {
+        MatchSettings { duration: 5.0, ..MatchSettings::default() }
+    }
+
+    fn entrants(specs: &[&str]) -> Vec<Entrant> {
+        specs.iter().map(|s| Entrant::parse(s).unwrap()).collect()
+    }
+
+    #[test]
+    fn same_seed_same_result() {
+        let e = entrants(&["chase", "regions:1/1"]);
+        let a = play_match(&short(), &e[0], &e[1], 42);
+        let b = play_match(&short(), &e[0], &e[1], 42);
+        assert_eq!((a.points_white, a.points_black), (b.points_white, b.points_black));
+        assert!(a.points_white + a.points_black > 0);
+
+        let legs = play_fair_pair(&short(), &e[0], &e[1], 42);
+        assert_eq!((legs[0].points_white, legs[0].points_black), (a.points_white, a.points_black));
+        let again = play_fair_pair(&short(), &e[0], &e[1], 42);
+        assert_eq!(fair_json(42, &legs), fair_json(42, &again));
+    }
+
+    #[test]
+    fn fair_totals_follow_the_entrant_across_legs() {
+        let legs = [MatchResult { points_white: 10, points_black: 4 }, MatchResult { points_white: 7, points_black: 3 }];
+        assert_eq!(fair_totals(&legs), (13, 11));
+        assert_eq!(fair_json(9, &legs),
+            "{\"seed\":9,\"legs\":[{\"white\":10,\"black\":4},{\"white\":7,\"black\":3}],\"first\":13,\"second\":11,\"verdict\":\"first\"}");
+        assert!(fair_json(9, &legs[..1]).ends_with("\"verdict\":null}"));
+    }
+
+    #[test]
+    fn elo_is_zero_sum_and_matrix_is_consistent() {
+        let e = entrants(&["chase", "wander", "none"]);
+        let seeds = 2;
+        let s = run(&e, &short(), seeds, 7);
+        assert_eq!(s.to_json(), run(&e, &short(), seeds, 7).to_json());
+
+        let total: f64 = s.ratings.iter().sum();
+        assert!((total - ELO_START * 3.0).abs() < 1e-9);
+        for a in 0..3 {
+            // Two legs per seed against each of the other two entrants
+            assert_eq!(s.totals[a].games(), seeds * 2 * 2);
+            for b in 0..3 {
+                let (ab, ba) = (s.matrix[a][b], s.matrix[b][a]);
+                assert_eq!((ab.wins, ab.draws, ab.losses), (ba.losses, ba.draws, ba.wins));
+            }
+        }
+    }
+
+    #[test]
+    fn elo_follows_the_standard_update() {
+        let e = entrants(&["chase", "none"]);
+        let legs = play_fair_pair(&short(), &e[0], &e[1], 3);
+        let s = run(&e, &short(), 1, 3);
+
+        // Replay the two updates by hand: leg one has chase white, leg two chase black
+        let mut r = [ELO_START, ELO_START];
+        for (white, black, res) in [(0, 1, legs[0]), (1, 0, legs[1])] {
+            let score = match res.points_white.cmp(&res.points_black) {
+                std::cmp::Ordering::Greater => 1.0,
+                std::cmp::Ordering::Equal => 0.5,
+                std::cmp::Ordering::Less => 0.0,
+            };
+            let expected = 1.0 / (1.0 + 10f64.powf((r[black] - r[white]) / 400.0));
+            r[white] += ELO_K * (score - expected);
+            r[black] -= ELO_K * (score - expected);
+        }
+        assert_eq!(s.ratings, r.to_vec());
+        assert_ne!(r[0], ELO_START);
+    }
+
+    #[test]
+    fn no_play_is_all_draws() {
+        let e = entrants(&["chase", "none"]);
+        let s = run(&e, &MatchSettings { duration: 0.0, ..short() }, 1, 1);
+        assert_eq!(s.ratings, vec![ELO_START, ELO_START]);
+        assert_eq!(s.to_csv(),
+            "rank,entrant,rating,games,wins,draws,losses\n1,chase,1500.0,2,0,2,0\n2,none,1500.0,2,0,2,0\n\nentrant,chase,none\nchase,-,0-2-0\nnone,0-2-0,-\n");
+    }
+}
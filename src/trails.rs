@@ -0,0 +1,88 @@
+use crate::render::{Paint, Renderer};
+use crate::sim::Ball;
+use crate::theme::{Theme, TrailKind};
+
+pub(crate) const MAX_LEN: usize = 48;
+const REF_SPEED: f64 = 300.0; // px/s at which a trail is drawn at full width
+
+/// Fixed-size ring of recent positions for one ball.
+#[derive(Clone, Copy)]
+struct Ring {
+    pts: [(f64, f64); MAX_LEN],
+    head: usize, // next write slot
+    len: usize,
+}
+
+impl Ring {
+    const EMPTY: Ring = Ring { pts: [(0.0, 0.0); MAX_LEN], head: 0, len: 0 };
+
+    fn push(&mut self, p: (f64, f64)) {
+        self.pts[self.head] = p;
+        self.head = (self.head + 1) % MAX_LEN;
+        self.len = (self.len + 1).min(MAX_LEN);
+    }
+
+    fn last(&self) -> Option<(f64, f64)> {
+        (self.len > 0).then(|| self.pts[(self.head + MAX_LEN - 1) % MAX_LEN])
+    }
+
+    /// Up to `n` points, newest first.
+    fn recent(&self, n: usize) -> impl Iterator<Item = (f64, f64)> + '_ {
+        (0..self.len.min(n)).map(move |k| self.pts[(self.head + MAX_LEN - 1 - k) % MAX_LEN])
+    }
+}
+
+/// Per-ball position history, sampled once per rendered frame and keyed by ball id. A
+/// jump longer than a quarter of the arena (a respawn or a wrap) restarts the trail.
+#[derive(Default)]
+pub(crate) struct Trails {
+    rings: Vec<(u32, Ring)>, // in `Sim::balls` order as of the last `record`
+}
+
+impl Trails {
+    pub fn clear(&mut self) { self.rings.clear(); }
+
+    pub fn record(&mut self, balls: &[Ball], width: f64, height: f64) {
+        let mut old = std::mem::take(&mut self.rings);
+        let jump = width.max(height) * 0.25;
+        for b in balls {
+            let mut ring = match old.iter().position(|&(id, _)| id == b.id) {
+                Some(i) => old.swap_remove(i).1,
+                None => Ring::EMPTY,
+            };
+            if let Some((x, y)) = ring.last() {
+                if (b.x - x).hypot(b.y - y) > jump { ring = Ring::EMPTY; }
+            }
+            ring.push((b.x, b.y));
+            self.rings.push((b.id, ring));
+        }
+    }
+
+    /// Draw under the balls: segments or ghost circles fading toward the tail, narrower
+    /// for slow balls.
+    pub fn draw(&self, r: &mut dyn Renderer, balls: &[Ball], theme: &Theme) {
+        let style = theme.trail;
+        if style.kind == TrailKind::Off || style.length < 2 { return; }
+        let n = style.length.min(MAX_LEN);
+        for ((id, ring), b) in self.rings.iter().zip(balls) {
+            if *id != b.id { continue; } // balls changed since the last `record`
+            let tint = theme.team(b.team).ball[1];
+            let width = b.size() * (b.vx.hypot(b.vy) / REF_SPEED).clamp(0.4, 1.5);
+            let pts: Vec<(f64, f64)> = ring.recent(n).collect();
+            for k in 1..pts.len() {
+                let fade = 1.0 - k as f64 / n as f64; // 1 at the ball, 0 at the tail
+                r.set_alpha(style.opacity * fade);
+                match style.kind {
+                    TrailKind::Line => r.stroke_polyline(&[pts[k - 1], pts[k]], tint, width * fade, None),
+                    TrailKind::Ghosts => {
+                        if k % 2 == 0 {
+                            r.fill_circle(pts[k].0, pts[k].1, width * (0.4 + 0.6 * fade), &Paint::Solid(tint));
+                        }
+                    }
+                    TrailKind::Off => {}
+                }
+            }
+        }
+        r.set_alpha(1.0);
+    }
+}